@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufWriter, Read, Write};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::fibonnacci::{fibonnacci_big_with, fibonnacci_with, FibAlgorithm, FibIndexing};
+use crate::lessons::LessonError;
+use crate::scalar::parse_scalar;
+
+// Anything above this is big enough to be worth handing to another thread.
+const PARALLEL_THRESHOLD: u64 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Csv,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "csv" => Ok(OutputFormat::Csv),
+            "json" => Ok(OutputFormat::Json),
+            other => Err(format!(
+                "unknown format `{other}`, expected text, csv or json"
+            )),
+        }
+    }
+}
+
+// - The memo is shared by every query in a batch, so asking for the same n
+//   twice only computes it once, even when the queries run on different
+//   threads.
+// - Values are stored already written out in decimal: for large n turning
+//   the number into digits takes longer than finding it, so that work is
+//   done on the worker threads as well.
+// - The Arc means handing a value out doesn't copy what may be tens of
+//   thousands of digits.
+#[derive(Default)]
+pub struct Memo {
+    values: Mutex<HashMap<u64, Arc<str>>>,
+}
+
+impl Memo {
+    pub fn get(&self, n: u64) -> Option<Arc<str>> {
+        self.values.lock().unwrap().get(&n).cloned()
+    }
+
+    // The lock isn't held while computing, so other threads can keep
+    // working; the queries are deduplicated up front so no work is repeated.
+    pub fn get_or_compute(&self, n: u64, algorithm: FibAlgorithm) -> Arc<str> {
+        if let Some(value) = self.get(n) {
+            return value;
+        }
+
+        let value: Arc<str> = match fibonnacci_with(n, algorithm) {
+            Some(small) => small.to_string().into(),
+            None => fibonnacci_big_with(n, algorithm).to_string().into(),
+        };
+
+        self.values
+            .lock()
+            .unwrap()
+            .entry(n)
+            .or_insert(value)
+            .clone()
+    }
+}
+
+// - Evaluates every query and returns the results in the same order.
+// - Small n are computed right away; the distinct large ones are spread over
+//   one thread per core, largest first so the slowest start earliest.
+pub fn evaluate(queries: &[u64], algorithm: FibAlgorithm) -> Vec<Arc<str>> {
+    let memo = Memo::default();
+
+    let mut large: Vec<u64> = queries
+        .iter()
+        .copied()
+        .filter(|&n| n > PARALLEL_THRESHOLD)
+        .collect();
+    large.sort_unstable_by(|a, b| b.cmp(a));
+    large.dedup();
+
+    let workers = thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1)
+        .min(large.len());
+    let next = Mutex::new(large.into_iter());
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let Some(n) = next.lock().unwrap().next() else {
+                    break;
+                };
+                memo.get_or_compute(n, algorithm);
+            });
+        }
+    });
+
+    queries
+        .iter()
+        .map(|&n| memo.get_or_compute(n, algorithm))
+        .collect()
+}
+
+// One n per line; blank lines are skipped and the line number is reported
+// along with the reason for anything that isn't a valid index.
+pub fn parse_queries(input: &str, indexing: FibIndexing) -> Result<Vec<u64>, LessonError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(number, line)| {
+            let invalid =
+                |reason: String| LessonError::InvalidArgs(format!("line {number}: {reason}"));
+
+            let n = parse_scalar::<u64>(line).map_err(|err| invalid(err.to_string()))?;
+            indexing
+                .to_standard(n)
+                .ok_or_else(|| invalid(String::from("the classic sequence starts at 1")))
+        })
+        .collect()
+}
+
+pub fn write_results(
+    out: &mut impl Write,
+    queries: &[u64],
+    results: &[Arc<str>],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Text => {
+            for value in results {
+                writeln!(out, "{value}")?;
+            }
+        }
+        OutputFormat::Csv => {
+            writeln!(out, "n,value")?;
+            for (n, value) in queries.iter().zip(results) {
+                writeln!(out, "{n},{value}")?;
+            }
+        }
+        // Values are written as strings, JSON numbers can't hold them exactly.
+        OutputFormat::Json => {
+            writeln!(out, "[")?;
+            for (i, (n, value)) in queries.iter().zip(results).enumerate() {
+                let comma = if i + 1 < results.len() { "," } else { "" };
+                writeln!(out, "  {{\"n\": {n}, \"value\": \"{value}\"}}{comma}")?;
+            }
+            writeln!(out, "]")?;
+        }
+    }
+
+    Ok(())
+}
+
+// Reads the queries from the file when one is given, stdin otherwise.
+pub fn run(
+    path: Option<&str>,
+    format: OutputFormat,
+    indexing: FibIndexing,
+    algorithm: FibAlgorithm,
+) -> Result<(), LessonError> {
+    let input = match path {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|err| io::Error::new(err.kind(), format!("{path}: {err}")))?,
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            input
+        }
+    };
+
+    let queries = parse_queries(&input, indexing)?;
+    let results = evaluate(&queries, algorithm);
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    write_results(&mut out, &queries, &results, format)?;
+    out.flush()?;
+
+    Ok(())
+}
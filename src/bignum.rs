@@ -0,0 +1,313 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Sub};
+use std::str::FromStr;
+
+// - BigUint is an unsigned integer that grows as large as it needs to.
+// - The value is kept as base 2^64 digits (limbs), least significant first.
+// - Zero is the empty list of limbs and there is never a trailing zero limb,
+//   which keeps comparisons and equality simple.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u64>,
+}
+
+// Largest power of ten that fits in a u64, used to convert to and from
+// decimal nineteen digits at a time.
+const DECIMAL_CHUNK: u64 = 10_000_000_000_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 19;
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn one() -> Self {
+        Self::from(1u64)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    // Number of bits needed to write the value, zero for zero.
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            None => 0,
+            Some(top) => self.limbs.len() as u64 * 64 - u64::from(top.leading_zeros()),
+        }
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.as_slice() {
+            [] => Some(0),
+            [limb] => Some(*limb),
+            _ => None,
+        }
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.limbs.as_slice() {
+            [] => Some(0),
+            [low] => Some(u128::from(*low)),
+            [low, high] => Some(u128::from(*high) << 64 | u128::from(*low)),
+            _ => None,
+        }
+    }
+
+    // Subtraction that returns None instead of going below zero.
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if *self < *other {
+            return None;
+        }
+
+        let mut limbs = self.limbs.clone();
+        let mut borrow = false;
+
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let rhs = other.limbs.get(i).copied().unwrap_or(0);
+            if rhs == 0 && !borrow && i >= other.limbs.len() {
+                break;
+            }
+
+            let (diff, b1) = limb.overflowing_sub(rhs);
+            let (diff, b2) = diff.overflowing_sub(u64::from(borrow));
+            *limb = diff;
+            borrow = b1 || b2;
+        }
+
+        Some(BigUint::from_limbs(limbs))
+    }
+
+    // Divides in place by a small divisor and returns the remainder.
+    pub fn div_rem_u64(&mut self, divisor: u64) -> u64 {
+        assert!(divisor != 0, "attempt to divide by zero");
+
+        let mut rem: u128 = 0;
+        for limb in self.limbs.iter_mut().rev() {
+            let acc = rem << 64 | u128::from(*limb);
+            *limb = (acc / u128::from(divisor)) as u64;
+            rem = acc % u128::from(divisor);
+        }
+
+        self.normalize();
+        rem as u64
+    }
+
+    pub fn rem_u64(&self, divisor: u64) -> u64 {
+        assert!(
+            divisor != 0,
+            "attempt to calculate the remainder with a divisor of zero"
+        );
+
+        self.limbs.iter().rev().fold(0u128, |rem, limb| {
+            (rem << 64 | u128::from(*limb)) % u128::from(divisor)
+        }) as u64
+    }
+
+    // base^exponent, by repeated squaring.
+    pub fn pow(base: u64, mut exponent: u32) -> BigUint {
+        let mut result = BigUint::one();
+        let mut base = BigUint::from(base);
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+
+        result
+    }
+
+    fn mul_add_u64(&mut self, factor: u64, addend: u64) {
+        let mut carry = u128::from(addend);
+        for limb in self.limbs.iter_mut() {
+            let acc = u128::from(*limb) * u128::from(factor) + carry;
+            *limb = acc as u64;
+            carry = acc >> 64;
+        }
+
+        if carry != 0 {
+            self.limbs.push(carry as u64);
+        }
+        self.normalize();
+    }
+
+    fn from_limbs(limbs: Vec<u64>) -> Self {
+        let mut value = Self { limbs };
+        value.normalize();
+        value
+    }
+
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self::from_limbs(vec![value])
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(value: u128) -> Self {
+        Self::from_limbs(vec![value as u64, (value >> 64) as u64])
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, other: &BigUint) {
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+
+        let mut carry = false;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let rhs = other.limbs.get(i).copied().unwrap_or(0);
+            if rhs == 0 && !carry && i >= other.limbs.len() {
+                break;
+            }
+
+            let (sum, c1) = limb.overflowing_add(rhs);
+            let (sum, c2) = sum.overflowing_add(u64::from(carry));
+            *limb = sum;
+            carry = c1 || c2;
+        }
+
+        if carry {
+            self.limbs.push(1);
+        }
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut sum = self.clone();
+        sum += other;
+        sum
+    }
+}
+
+impl Sub<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    // Panics on underflow just like the primitive unsigned types do in debug.
+    fn sub(self, other: &BigUint) -> BigUint {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    // Schoolbook multiplication.
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+
+        let mut limbs = vec![0u64; self.limbs.len() + other.limbs.len()];
+
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry: u128 = 0;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let acc = u128::from(a) * u128::from(b) + u128::from(limbs[i + j]) + carry;
+                limbs[i + j] = acc as u64;
+                carry = acc >> 64;
+            }
+            limbs[i + other.limbs.len()] = carry as u64;
+        }
+
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.pad_integral(true, "", "0");
+        }
+
+        let mut rest = self.clone();
+        let mut chunks = Vec::new();
+        while !rest.is_zero() {
+            chunks.push(rest.div_rem_u64(DECIMAL_CHUNK));
+        }
+
+        let mut digits = chunks.pop().map(|top| top.to_string()).unwrap_or_default();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{chunk:0width$}", width = DECIMAL_CHUNK_DIGITS));
+        }
+
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBigUintError {
+    Empty,
+    InvalidDigit(char),
+}
+
+impl fmt::Display for ParseBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBigUintError::Empty => f.write_str("cannot parse a number from an empty string"),
+            ParseBigUintError::InvalidDigit(c) => write!(f, "invalid digit `{c}` in number"),
+        }
+    }
+}
+
+impl Error for ParseBigUintError {}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    // Accepts plain decimal digits, with _ allowed as a separator.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut value = BigUint::zero();
+        let mut seen_digit = false;
+
+        for c in s.chars() {
+            if c == '_' {
+                continue;
+            }
+
+            let digit = c.to_digit(10).ok_or(ParseBigUintError::InvalidDigit(c))?;
+            value.mul_add_u64(10, u64::from(digit));
+            seen_digit = true;
+        }
+
+        if seen_digit {
+            Ok(value)
+        } else {
+            Err(ParseBigUintError::Empty)
+        }
+    }
+}
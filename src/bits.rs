@@ -0,0 +1,269 @@
+use std::num::FpCategory;
+
+use crate::cli::ToolResult;
+use crate::floats::{FloatType, FloatValue};
+use crate::ints::{IntType, IntValue};
+use crate::literal::{parse_literal, parse_literal_as, LiteralError};
+
+const USAGE: &str = "usage: cpc bits <value> [--type <type>]";
+
+// Puts a _ between every `size` digits, counting from the right, the way
+// 0b1111_0000 is written in data_types.rs.
+fn grouped(digits: &str, size: usize) -> String {
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(size) {
+            out.push('_');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn binary(bits: u128, width: u32) -> String {
+    grouped(&format!("{bits:0width$b}", width = width as usize), 4)
+}
+
+// Decimal, hex, octal and binary all show the same bits; for negative
+// numbers that means the two's complement pattern, like {:x} does.
+fn print_radixes(decimal: String, bits: u128, width: u32) {
+    let hex = format!("{bits:0width$x}", width = width as usize / 4);
+
+    println!("  decimal:  {decimal}");
+    println!("  hex:      0x{}", grouped(&hex, 4));
+    println!("  octal:    0o{bits:o}");
+    println!("  binary:   0b{}", binary(bits, width));
+}
+
+pub fn print_int(value: IntValue) {
+    let ty = value.ty();
+    let width = ty.bits();
+    let bits = value.to_bits();
+    let signedness = if ty.is_signed() { "signed" } else { "unsigned" };
+
+    println!("  type:     {ty} ({width} bits, {signedness})");
+    print_radixes(value.to_string(), bits, width);
+
+    if !ty.is_signed() {
+        println!("  layout:   {} (no sign bit)", binary(bits, width));
+        print_counterpart(ty, bits);
+        return;
+    }
+
+    // The top bit is worth -2^(n - 1) instead of 2^(n - 1); every other bit
+    // keeps its usual weight.
+    let rest_width = width - 1;
+    let rest = bits & (u128::MAX >> (128 - rest_width));
+    let negative = bits >> rest_width == 1;
+
+    println!(
+        "  layout:   {} | {}",
+        u8::from(negative),
+        grouped(&format!("{rest:0w$b}", w = rest_width as usize), 4)
+    );
+    if negative {
+        println!(
+            "            sign bit worth {}, plus {rest} from the other bits = {value}",
+            ty.min()
+        );
+
+        let magnitude = bits.wrapping_neg() & (u128::MAX >> (128 - width));
+        let inverted = !magnitude & (u128::MAX >> (128 - width));
+        println!(
+            "  negation: {magnitude} is {}, invert every bit to get {} and add 1 to get {}",
+            binary(magnitude, width),
+            binary(inverted, width),
+            binary(bits, width)
+        );
+    } else {
+        println!("            sign bit clear, the other bits give {value}");
+    }
+
+    print_counterpart(ty, bits);
+}
+
+// The same bits read with the other signedness, what `as` does between
+// types of the same width.
+fn print_counterpart(ty: IntType, bits: u128) {
+    let label = format!("as {}:", ty.counterpart());
+    println!(
+        "  {label:<10}{}",
+        IntValue::from_bits(ty.counterpart(), bits)
+    );
+}
+
+// - The stored exponent is biased so it can be kept unsigned; subtracting
+//   the bias gives the real power of two.
+// - Normal numbers leave out the leading 1 of the significand, an exponent
+//   of all zeros marks the subnormals that don't have one, and all ones
+//   marks infinity and NaN.
+pub fn print_float(value: FloatValue) {
+    let ty = value.ty();
+    let width = ty.bits();
+    let bits = value.to_bits();
+    let parts = value.parts();
+    let exponent_bits = ty.exponent_bits() as usize;
+    let mantissa_bits = ty.mantissa_bits() as usize;
+
+    println!(
+        "  type:     {ty} ({width} bits: 1 sign, {exponent_bits} exponent, {mantissa_bits} mantissa)"
+    );
+    print_radixes(value.to_string(), bits.into(), width);
+
+    let exponent = format!("{:0exponent_bits$b}", parts.exponent);
+    let mantissa = format!("{:0mantissa_bits$b}", parts.mantissa);
+    println!(
+        "  layout:   {} | {exponent} | {mantissa}",
+        u8::from(parts.negative)
+    );
+
+    let sign = if parts.negative { "-" } else { "+" };
+    println!(
+        "  sign:     {} ({})",
+        u8::from(parts.negative),
+        if parts.negative {
+            "negative"
+        } else {
+            "positive"
+        }
+    );
+
+    let bias = ty.bias();
+    let fraction = parts.mantissa as f64 / (1u64 << mantissa_bits) as f64;
+    let stored = parts.exponent as i32;
+
+    match value.classify() {
+        FpCategory::Nan => {
+            println!("  exponent: {exponent}, all ones");
+            println!("  mantissa: {mantissa}, not zero");
+            println!("  value:    NaN");
+        }
+        FpCategory::Infinite => {
+            println!("  exponent: {exponent}, all ones");
+            println!("  mantissa: {mantissa}, zero");
+            println!("  value:    {sign}infinity");
+        }
+        FpCategory::Zero => {
+            println!("  exponent: {exponent}, all zeros");
+            println!("  mantissa: {mantissa}, zero");
+            println!("  value:    {sign}0");
+        }
+        FpCategory::Subnormal => {
+            println!(
+                "  exponent: {exponent}, all zeros, subnormal: 1 - {bias} = {}",
+                1 - bias
+            );
+            println!(
+                "  mantissa: {} / 2^{mantissa_bits} = {fraction}, no leading 1",
+                parts.mantissa
+            );
+            println!("  value:    {sign}{fraction} × 2^{}", 1 - bias);
+        }
+        FpCategory::Normal => {
+            println!(
+                "  exponent: {exponent} = {stored}, {stored} - {bias} = {}",
+                stored - bias
+            );
+            println!(
+                "  mantissa: 1 + {} / 2^{mantissa_bits} = {}",
+                parts.mantissa,
+                1.0 + fraction
+            );
+            println!("  value:    {sign}{} × 2^{}", 1.0 + fraction, stored - bias);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Type {
+    Int(IntType),
+    Float(FloatType),
+}
+
+fn parse_type(name: &str) -> Result<Type, String> {
+    if let Ok(ty) = name.parse() {
+        return Ok(Type::Int(ty));
+    }
+    if let Ok(ty) = name.parse() {
+        return Ok(Type::Float(ty));
+    }
+
+    let names: Vec<&str> = IntType::ALL
+        .iter()
+        .map(|ty| ty.name())
+        .chain(FloatType::ALL.iter().map(|ty| ty.name()))
+        .collect();
+    Err(format!(
+        "unknown type `{name}`, expected one of {}",
+        names.join(", ")
+    ))
+}
+
+// - Without --type the value is read the way the compiler would: 2.0 and
+//   1e3 are floats, 0.5f32 is an f32, everything else is an integer literal.
+// - An integer suffix settles it first, the e in 5usize isn't an exponent.
+fn looks_like_float(text: &str) -> bool {
+    let text = text.trim().trim_start_matches('-').to_ascii_lowercase();
+    if ["0x", "0o", "0b", "b'"].iter().any(|p| text.starts_with(p))
+        || IntType::ALL.iter().any(|ty| text.ends_with(ty.name()))
+    {
+        return false;
+    }
+
+    // An e is only an exponent right after a digit, five is not a float.
+    let exponent = text
+        .as_bytes()
+        .windows(2)
+        .any(|pair| pair[0].is_ascii_digit() && pair[1] == b'e');
+
+    text.contains('.')
+        || exponent
+        || text.ends_with("f32")
+        || text.ends_with("f64")
+        || ["inf", "infinity", "nan"].contains(&text.as_str())
+}
+
+// The type `cpc bits` shows a value as when no --type is given.
+pub fn infer_type(value: &str) -> Result<Type, LiteralError> {
+    if looks_like_float(value) {
+        let found = FloatType::ALL
+            .into_iter()
+            .find(|ty| value.trim().ends_with(ty.name()));
+        return Ok(Type::Float(found.unwrap_or(FloatType::F64)));
+    }
+    Ok(Type::Int(parse_literal(value)?.value.ty()))
+}
+
+// - cpc bits -5 --type i8 shows how -5 is laid out in an i8.
+// - Without --type an integer suffix, a float suffix or the compiler's
+//   defaults (i32 and f64) decide the type.
+pub fn bits_command(args: &[String]) -> ToolResult {
+    let mut value = None;
+    let mut ty = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--type" => {
+                let name = args.next().ok_or("`--type` needs a value")?;
+                ty = Some(parse_type(name)?);
+            }
+            _ if value.is_none() => value = Some(arg.as_str()),
+            _ => return Err(USAGE.into()),
+        }
+    }
+    let value = value.ok_or(USAGE)?;
+
+    let ty = match ty {
+        Some(ty) => ty,
+        None => infer_type(value)?,
+    };
+
+    println!("{}", value.trim());
+    match ty {
+        Type::Int(ty) => print_int(parse_literal_as(value, ty)?),
+        Type::Float(ty) => print_float(FloatValue::parse(value, ty)?),
+    }
+
+    Ok(())
+}
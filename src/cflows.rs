@@ -1,9 +1,37 @@
+use crate::lessons::{Context, Lesson, LessonError};
+
+pub struct ControlFlow;
+
+impl Lesson for ControlFlow {
+    fn id(&self) -> &'static str {
+        "control-flow"
+    }
+
+    fn title(&self) -> &'static str {
+        "Control Flow"
+    }
+
+    fn summary(&self) -> &'static str {
+        "if expressions and loops"
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["functions"]
+    }
+
+    fn run(&self, ctx: &mut Context) -> Result<(), LessonError> {
+        ctx.expect_no_args(self.id())?;
+        run();
+        Ok(())
+    }
+}
+
 // - The ability to run some code depending on whether a condition is true and
 //   to run some code repeatedly while a condition is true are basic building blocks
 //   in most programming languages.
 // - The most common constructs that let you control the flow of execution of Rust
 //   code are if expressions and loops.
-fn main() {
+pub fn run() {
     // - An if expression allows you to branch your code depending on conditions.
     // - You provide a condition and then state, "If this condition is met, run
     //   this block of code. If the condition is not met, do not run this block
@@ -71,6 +99,8 @@ fn main() {
     let condition = true;
     let number = if condition { 5 } else { 6 };
 
+    println!("The value of number is: {number}");
+
     // The number variable will be bound to value based on the outcome of the if
     // expression.
 
@@ -228,7 +258,7 @@ fn main() {
 
     println!("LIFTOFF!!!");
 
-    for i in (1..=100) {
+    for i in 1..=100 {
         println!("{i}");
     }
 }
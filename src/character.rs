@@ -0,0 +1,443 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::cli::ToolResult;
+use crate::numeric::checked_add_n;
+
+// - Grown out of some_function in the functions lesson, which kept a Diablo
+//   IV paragon level in a u8 even though paragon goes past 255.
+// - The total XP is the only thing stored, level and paragon are worked out
+//   from it with the curve below, so they can never disagree with it.
+// - Levels go up to 60 and fit a u8, paragon levels up to 300 need a u16.
+//   XP is a u64 since it keeps counting past the end of the curve.
+
+pub const MAX_LEVEL: u8 = 60;
+pub const MAX_PARAGON: u16 = 300;
+
+const MAX_NAME_LEN: usize = 32;
+
+// XP to go from `level` to the one after it, 100 × level².
+pub fn xp_for_level(level: u8) -> u64 {
+    assert!(
+        (1..MAX_LEVEL).contains(&level),
+        "there is no level after {level}"
+    );
+    100 * u64::from(level).pow(2)
+}
+
+// XP to go from paragon level `paragon` to the next, 50,000 plus 5,000 more
+// for every paragon level already earned.
+pub fn xp_for_paragon(paragon: u16) -> u64 {
+    assert!(
+        paragon < MAX_PARAGON,
+        "there is no paragon level after {paragon}"
+    );
+    50_000 + 5_000 * u64::from(paragon)
+}
+
+// Total XP a new character needs to reach `level`.
+pub fn total_xp_for_level(level: u8) -> u64 {
+    (1..level).map(xp_for_level).sum()
+}
+
+// Total XP a new character needs to reach paragon level `paragon`, which is
+// only earned after MAX_LEVEL.
+pub fn total_xp_for_paragon(paragon: u16) -> u64 {
+    total_xp_for_level(MAX_LEVEL) + (0..paragon).map(xp_for_paragon).sum::<u64>()
+}
+
+// - Where a total XP lands on the curve.
+// - `into_next` is the XP earned towards the next level or paragon level,
+//   `next` what that one costs in full. Past MAX_PARAGON there's no next
+//   one and `into_next` is whatever was earned beyond it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Standing {
+    pub level: u8,
+    pub paragon: u16,
+    pub into_next: u64,
+    pub next: Option<u64>,
+}
+
+pub fn standing(xp: u64) -> Standing {
+    let mut left = xp;
+
+    let mut level = 1;
+    while level < MAX_LEVEL {
+        let cost = xp_for_level(level);
+        if left < cost {
+            return Standing {
+                level,
+                paragon: 0,
+                into_next: left,
+                next: Some(cost),
+            };
+        }
+        left -= cost;
+        level += 1;
+    }
+
+    let mut paragon = 0;
+    while paragon < MAX_PARAGON {
+        let cost = xp_for_paragon(paragon);
+        if left < cost {
+            return Standing {
+                level,
+                paragon,
+                into_next: left,
+                next: Some(cost),
+            };
+        }
+        left -= cost;
+        paragon += 1;
+    }
+
+    Standing {
+        level,
+        paragon,
+        into_next: left,
+        next: None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CharacterError {
+    InvalidName(String),
+    UnknownCharacter(String),
+    AlreadyExists(String),
+    InvalidXp(String),
+    XpOverflow { xp: u64, gained: u64 },
+}
+
+impl fmt::Display for CharacterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CharacterError::InvalidName(name) => write!(
+                f,
+                "`{name}` can't be used as a name, it needs 1 to {MAX_NAME_LEN} characters on a single line"
+            ),
+            CharacterError::UnknownCharacter(name) => write!(
+                f,
+                "there is no character called `{name}`, create one with `cpc character new {name}`"
+            ),
+            CharacterError::AlreadyExists(name) => {
+                write!(f, "there already is a character called `{name}`")
+            }
+            CharacterError::InvalidXp(xp) => write!(f, "`{xp}` is not an amount of XP"),
+            CharacterError::XpOverflow { xp, gained } => write!(
+                f,
+                "{xp} + {gained} XP doesn't fit in a u64, the most a character can hold is {}",
+                u64::MAX
+            ),
+        }
+    }
+}
+
+impl Error for CharacterError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Character {
+    name: String,
+    xp: u64,
+}
+
+impl Character {
+    // A fresh character at level 1 with no XP.
+    pub fn new(name: &str) -> Result<Character, CharacterError> {
+        Character::with_xp(name, 0)
+    }
+
+    pub fn with_xp(name: &str, xp: u64) -> Result<Character, CharacterError> {
+        let trimmed = name.trim();
+        if trimmed.is_empty()
+            || trimmed.chars().count() > MAX_NAME_LEN
+            || trimmed.contains(['\n', '\r'])
+        {
+            return Err(CharacterError::InvalidName(name.to_string()));
+        }
+
+        Ok(Character {
+            name: trimmed.to_string(),
+            xp,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn xp(&self) -> u64 {
+        self.xp
+    }
+
+    pub fn level(&self) -> u8 {
+        self.standing().level
+    }
+
+    pub fn paragon(&self) -> u16 {
+        self.standing().paragon
+    }
+
+    pub fn standing(&self) -> Standing {
+        standing(self.xp)
+    }
+
+    // Returns where the character stood before, to compare against.
+    pub fn add_xp(&mut self, gained: u64) -> Result<Standing, CharacterError> {
+        let before = self.standing();
+        self.xp = checked_add_n(self.xp, gained).ok_or(CharacterError::XpOverflow {
+            xp: self.xp,
+            gained,
+        })?;
+        Ok(before)
+    }
+}
+
+impl fmt::Display for Character {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let standing = self.standing();
+        write!(f, "{}, level {}", self.name, standing.level)?;
+        if standing.level == MAX_LEVEL {
+            write!(f, ", paragon {}", standing.paragon)?;
+        }
+        Ok(())
+    }
+}
+
+// - The characters cpc knows about, kept between runs like Progress is.
+// - The file has one character per line, the name and then the total XP,
+//   `Ayla 7021000`, so it can be edited by hand too.
+#[derive(Debug, Default)]
+pub struct Profiles {
+    characters: BTreeMap<String, Character>,
+    path: Option<PathBuf>,
+}
+
+impl Profiles {
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    // CPC_CHARACTERS wins over the default location of ~/.cpc/characters.
+    pub fn default_path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os("CPC_CHARACTERS") {
+            return Some(PathBuf::from(path));
+        }
+
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cpc").join("characters"))
+    }
+
+    pub fn load(path: PathBuf) -> io::Result<Self> {
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+
+        let mut characters = BTreeMap::new();
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let character = line
+                .rsplit_once(' ')
+                .and_then(|(name, xp)| Character::with_xp(name, xp.parse().ok()?).ok())
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "{}:{}: expected a name and an amount of XP, found `{line}`",
+                            path.display(),
+                            number + 1
+                        ),
+                    )
+                })?;
+            characters.insert(character.name().to_string(), character);
+        }
+
+        Ok(Self {
+            characters,
+            path: Some(path),
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Character> {
+        self.characters.get(name.trim())
+    }
+
+    pub fn characters(&self) -> impl Iterator<Item = &Character> {
+        self.characters.values()
+    }
+
+    // Adds the character, or replaces the one with the same name.
+    pub fn save_character(&mut self, character: Character) -> io::Result<()> {
+        self.characters
+            .insert(character.name().to_string(), character);
+        self.save()
+    }
+
+    // Returns false when there was no such character.
+    pub fn remove(&mut self, name: &str) -> io::Result<bool> {
+        if self.characters.remove(name.trim()).is_none() {
+            return Ok(false);
+        }
+
+        self.save()?;
+        Ok(true)
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut contents = String::new();
+        for character in self.characters.values() {
+            contents.push_str(&format!("{} {}\n", character.name(), character.xp()));
+        }
+
+        fs::write(path, contents)
+    }
+}
+
+const BAR_WIDTH: usize = 20;
+
+// `[######..............] 31%`
+fn bar(done: u64, total: u64) -> String {
+    let filled = (u128::from(done) * BAR_WIDTH as u128 / u128::from(total)) as usize;
+    let percent = u128::from(done) * 100 / u128::from(total);
+    format!(
+        "[{}{}] {percent}%",
+        "#".repeat(filled),
+        ".".repeat(BAR_WIDTH - filled)
+    )
+}
+
+fn print_character(character: &Character) {
+    let standing = character.standing();
+    println!("{character}");
+    println!("  {} XP in total", character.xp());
+
+    match standing.next {
+        Some(cost) => {
+            let next = if standing.level < MAX_LEVEL {
+                format!("level {}", standing.level + 1)
+            } else {
+                format!("paragon {}", standing.paragon + 1)
+            };
+            println!(
+                "  {next}: {} / {cost} XP {}",
+                standing.into_next,
+                bar(standing.into_next, cost)
+            );
+        }
+        None => println!("  paragon {MAX_PARAGON} is as far as it goes"),
+    }
+}
+
+// Every level, then every 50th paragon level.
+fn print_curve() {
+    println!("{:>8}{:>12}{:>14}", "level", "to next", "total");
+    for level in 1..=MAX_LEVEL {
+        let next = if level < MAX_LEVEL {
+            xp_for_level(level).to_string()
+        } else {
+            String::from("-")
+        };
+        println!("{level:>8}{next:>12}{:>14}", total_xp_for_level(level));
+    }
+
+    println!();
+    println!("{:>8}{:>12}{:>14}", "paragon", "to next", "total");
+    for paragon in (0..=MAX_PARAGON).step_by(50) {
+        let next = if paragon < MAX_PARAGON {
+            xp_for_paragon(paragon).to_string()
+        } else {
+            String::from("-")
+        };
+        println!(
+            "{paragon:>8}{next:>12}{:>14}",
+            total_xp_for_paragon(paragon)
+        );
+    }
+}
+
+// - cpc character new <name> and delete <name> manage the profiles.
+// - cpc character add <name> <xp> adds XP and reports any levels gained.
+// - cpc character show [name] shows one character's progress, or everyone's.
+// - cpc character curve prints the XP needed for each level.
+pub fn character_command(args: &[String]) -> ToolResult {
+    const USAGE: &str =
+        "usage: cpc character new <name> | add <name> <xp> | show [name] | delete <name> | curve";
+
+    let mut profiles = match Profiles::default_path() {
+        Some(path) => Profiles::load(path)?,
+        None => Profiles::in_memory(),
+    };
+    let find = |profiles: &Profiles, name: &str| {
+        profiles
+            .get(name)
+            .cloned()
+            .ok_or_else(|| CharacterError::UnknownCharacter(name.trim().to_string()))
+    };
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["new", name] => {
+            let character = Character::new(name)?;
+            if profiles.get(name).is_some() {
+                return Err(CharacterError::AlreadyExists(character.name().to_string()).into());
+            }
+            print_character(&character);
+            profiles.save_character(character)?;
+        }
+        ["add", name, xp] => {
+            let gained = xp
+                .replace('_', "")
+                .parse()
+                .map_err(|_| CharacterError::InvalidXp(xp.to_string()))?;
+            let mut character = find(&profiles, name)?;
+            let before = character.add_xp(gained)?;
+            let after = character.standing();
+
+            println!("+{gained} XP");
+            if after.level > before.level {
+                println!("Level up! {} → {}", before.level, after.level);
+            }
+            if after.paragon > before.paragon {
+                println!("Paragon up! {} → {}", before.paragon, after.paragon);
+            }
+            print_character(&character);
+            profiles.save_character(character)?;
+        }
+        ["show", name] => print_character(&find(&profiles, name)?),
+        ["show"] => {
+            let mut characters = profiles.characters().peekable();
+            if characters.peek().is_none() {
+                println!("No characters yet, create one with `cpc character new <name>`.");
+            }
+            for character in characters {
+                print_character(character);
+            }
+        }
+        ["delete", name] => {
+            if !profiles.remove(name)? {
+                return Err(CharacterError::UnknownCharacter(name.trim().to_string()).into());
+            }
+            println!("Deleted {}.", name.trim());
+        }
+        ["curve"] => print_curve(),
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(())
+}
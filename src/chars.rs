@@ -0,0 +1,199 @@
+use crate::cli::ToolResult;
+use crate::general_category::{general_category, GeneralCategory};
+
+const ZWJ: char = '\u{200D}';
+
+// Marks and modifiers that never start a grapheme cluster of their own.
+fn is_extend(c: char) -> bool {
+    matches!(
+        general_category(c),
+        GeneralCategory::NonspacingMark | GeneralCategory::EnclosingMark
+    ) || matches!(c, '\u{200C}' | '\u{1F3FB}'..='\u{1F3FF}' | '\u{E0020}'..='\u{E007F}')
+}
+
+// Controls, separators and format characters like U+200B stand alone,
+// apart from the ZWJ and the format characters that extend the cluster
+// before them: the ZWNJ and the emoji tags.
+fn is_control(c: char) -> bool {
+    c != ZWJ
+        && !is_extend(c)
+        && matches!(
+            general_category(c),
+            GeneralCategory::Control
+                | GeneralCategory::Format
+                | GeneralCategory::LineSeparator
+                | GeneralCategory::ParagraphSeparator
+        )
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c, '\u{1F1E6}'..='\u{1F1FF}')
+}
+
+// The blocks emoji are drawn from, standing in for the
+// Extended_Pictographic property.
+fn is_pictographic(c: char) -> bool {
+    matches!(
+        c,
+        '\u{A9}'
+            | '\u{AE}'
+            | '\u{203C}'
+            | '\u{2049}'
+            | '\u{2122}'
+            | '\u{2139}'
+            | '\u{2194}'..='\u{21FF}'
+            | '\u{2300}'..='\u{23FF}'
+            | '\u{25A0}'..='\u{27BF}'
+            | '\u{2B00}'..='\u{2BFF}'
+            | '\u{1F000}'..='\u{1F1E5}'
+            | '\u{1F200}'..='\u{1FAFF}'
+    )
+}
+
+// - Splits text into grapheme clusters, what a reader would call one
+//   character, following the Unicode segmentation rules for:
+//     \r\n, combining marks, skin tones, zero width joiner sequences like
+//     👨‍👩‍👧, flags made of two regional indicators, and tag sequences
+// - Hangul jamo sequences and prepended marks aren't joined, those need
+//   properties the bundled table doesn't have.
+pub fn graphemes(text: &str) -> Vec<&str> {
+    let mut clusters = Vec::new();
+    let mut start = 0;
+    let mut prev: Option<char> = None;
+    // Whether the cluster so far is a pictograph followed only by extenders,
+    // which a ZWJ can join to the next pictograph.
+    let mut pictographic = false;
+    let mut regional = 0;
+
+    for (i, c) in text.char_indices() {
+        if let Some(p) = prev {
+            let join = (p == '\r' && c == '\n')
+                || (!is_control(p)
+                    && (is_extend(c)
+                        || c == ZWJ
+                        || general_category(c) == GeneralCategory::SpacingMark))
+                || (p == ZWJ && pictographic && is_pictographic(c))
+                || (is_regional_indicator(c) && regional % 2 == 1);
+
+            if !join {
+                clusters.push(&text[start..i]);
+                start = i;
+            }
+        }
+
+        if is_pictographic(c) {
+            pictographic = true;
+        } else if !is_extend(c) && c != ZWJ {
+            pictographic = false;
+        }
+        regional = if is_regional_indicator(c) {
+            regional + 1
+        } else {
+            0
+        };
+        prev = Some(c);
+    }
+
+    if start < text.len() {
+        clusters.push(&text[start..]);
+    }
+    clusters
+}
+
+// What a char is doing inside a longer grapheme cluster.
+fn role(c: char) -> Option<&'static str> {
+    let role = match c {
+        ZWJ => "zero width joiner",
+        '\u{FE0F}' => "emoji presentation selector",
+        '\u{FE0E}' => "text presentation selector",
+        '\u{1F3FB}'..='\u{1F3FF}' => "skin tone modifier",
+        '\u{E0020}'..='\u{E007F}' => "tag",
+        c if is_regional_indicator(c) => "regional indicator",
+        c => match general_category(c) {
+            GeneralCategory::NonspacingMark | GeneralCategory::EnclosingMark => "combining mark",
+            GeneralCategory::SpacingMark => "spacing mark",
+            _ => return None,
+        },
+    };
+    Some(role)
+}
+
+fn scalar(c: char) -> String {
+    format!("U+{:04X}", c as u32)
+}
+
+fn utf8(c: char) -> String {
+    let mut buffer = [0; 4];
+    let bytes: Vec<String> = c
+        .encode_utf8(&mut buffer)
+        .bytes()
+        .map(|b| format!("{b:02X}"))
+        .collect();
+    bytes.join(" ")
+}
+
+fn utf16(c: char) -> String {
+    let mut buffer = [0; 2];
+    let units: Vec<String> = c
+        .encode_utf16(&mut buffer)
+        .iter()
+        .map(|unit| format!("{unit:04X}"))
+        .collect();
+    units.join(" ")
+}
+
+// - cpc char "ℤ🤗" lists every char with its scalar value, how UTF-8 and
+//   UTF-16 encode it and its general category.
+// - Then the grapheme clusters, to show where one visible character is made
+//   of several chars.
+pub fn char_command(args: &[String]) -> ToolResult {
+    if args.is_empty() {
+        return Err("usage: cpc char <text>".into());
+    }
+    let text = args.join(" ");
+
+    println!("\"{text}\"");
+    println!(
+        "  {:<10}{:<13}{:<11}{:<26}char",
+        "scalar", "utf-8", "utf-16", "category"
+    );
+    for c in text.chars() {
+        let category = general_category(c);
+        println!(
+            "  {:<10}{:<13}{:<11}{:<26}'{}'",
+            scalar(c),
+            utf8(c),
+            utf16(c),
+            format!("{} {}", category.abbreviation(), category.name()),
+            c.escape_debug()
+        );
+    }
+
+    let clusters = graphemes(&text);
+    println!();
+    println!("  text.len():               {} bytes of UTF-8", text.len());
+    println!(
+        "  text.encode_utf16():      {} units",
+        text.encode_utf16().count()
+    );
+    println!("  text.chars().count():     {} chars", text.chars().count());
+    println!("  grapheme clusters:        {}", clusters.len());
+
+    for cluster in clusters {
+        let count = cluster.chars().count();
+        if count == 1 {
+            continue;
+        }
+
+        println!();
+        println!("  \"{cluster}\" is one grapheme cluster made of {count} chars:");
+        for c in cluster.chars() {
+            match role(c) {
+                Some(role) => println!("    {:<10}{role}", scalar(c)),
+                None => println!("    {:<10}{}", scalar(c), c.escape_debug()),
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use std::error::Error;
+
+use crate::lessons::{Context, Registry};
+use crate::progress::Progress;
+use crate::{
+    bits, character, chars, destructure, eval, fibonnacci, floats, literal, overflow, recurrence,
+    temperature, units, zeckendorf,
+};
+
+// - Tools are calculators built on top of the lessons. Unlike lessons they
+//   have no prerequisites and don't count towards progress.
+// - `run` receives the arguments that followed the tool's name.
+pub struct Tool {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub summary: &'static str,
+    pub run: fn(&[String]) -> ToolResult,
+}
+
+pub type ToolResult = Result<(), Box<dyn Error>>;
+
+pub const TOOLS: &[Tool] = &[
+    Tool {
+        name: "pisano",
+        usage: "pisano <m>",
+        summary: "Length of the cycle the Fibonnacci numbers repeat mod m",
+        run: fibonnacci::pisano_command,
+    },
+    Tool {
+        name: "zeckendorf",
+        usage: "zeckendorf <n>",
+        summary: "Write n as a sum of non-consecutive Fibonnacci numbers",
+        run: zeckendorf::zeckendorf_command,
+    },
+    Tool {
+        name: "fibcode",
+        usage: "fibcode encode|decode [--text]",
+        summary: "Fibonnacci-code the integers on stdin, or decode them",
+        run: zeckendorf::fibcode_command,
+    },
+    Tool {
+        name: "seq",
+        usage: "seq --name <sequence> (--n <n> | --terms <k>) [--matrix]\n\
+                seq --coeffs <c1,..> --seed <a0,..> (--n <n> | --terms <k>) [--matrix]",
+        summary: "Terms of Lucas, Tribonacci, Pell, Padovan or any linear recurrence",
+        run: recurrence::seq_command,
+    },
+    Tool {
+        name: "literal",
+        usage: "literal <literal>",
+        summary: "Parse an integer literal like 0b1111_0000u16 or b'A'",
+        run: literal::literal_command,
+    },
+    Tool {
+        name: "bits",
+        usage: "bits <value> [--type i8|u16|i32|f32|..]",
+        summary: "Show a value in every base and how its bits are laid out",
+        run: bits::bits_command,
+    },
+    Tool {
+        name: "overflow",
+        usage: "overflow <a> <op> <b> [--type <type> | --table]",
+        summary: "Compare wrapping, checked, saturating and overflowing arithmetic",
+        run: overflow::overflow_command,
+    },
+    Tool {
+        name: "float",
+        usage: "float <number> | <a> <+|-|*|/> <b>",
+        summary: "Exact values, neighbours and rounding error of f32 and f64 results",
+        run: floats::float_command,
+    },
+    Tool {
+        name: "eval",
+        usage: "eval <expression>",
+        summary: "Evaluate arithmetic with Rust's typing, division and overflow rules",
+        run: eval::eval_command,
+    },
+    Tool {
+        name: "char",
+        usage: "char <text>",
+        summary: "Scalar values, UTF-8 and UTF-16 encodings and grapheme clusters of text",
+        run: chars::char_command,
+    },
+    Tool {
+        name: "let",
+        usage: "let \"let <pattern> = <value>\"",
+        summary: "Destructure tuples and arrays and show each binding's type and size",
+        run: destructure::let_command,
+    },
+    Tool {
+        name: "convert",
+        usage: "convert <value><unit> [to] <unit>",
+        summary: "Convert lengths, masses, times, temperatures and data sizes",
+        run: units::convert_command,
+    },
+    Tool {
+        name: "temp",
+        usage: "temp <value> <unit> | --from <a> --to <b> [--step <n>] [--unit C|F|K|R]",
+        summary: "Convert temperatures exactly or print a conversion table",
+        run: temperature::temp_command,
+    },
+    Tool {
+        name: "character",
+        usage: "character new <name> | add <name> <xp> | show [name] | delete <name> | curve",
+        summary: "Track a character's level, paragon and XP between runs",
+        run: character::character_command,
+    },
+];
+
+// - `args` is everything after the program name.
+// - With no arguments, or with help/-h/--help, the usage is printed.
+// - Anything that isn't a built-in command or a tool is looked up in the
+//   registry.
+pub fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let registry = Registry::curriculum();
+
+    let Some((name, rest)) = args.split_first() else {
+        print_usage(&registry);
+        return Ok(());
+    };
+
+    if let Some(tool) = TOOLS.iter().find(|tool| tool.name == name) {
+        return (tool.run)(rest);
+    }
+
+    let progress = match Progress::default_path() {
+        Some(path) => Progress::load(path)?,
+        None => Progress::in_memory(),
+    };
+    let mut ctx = Context::new(rest.to_vec(), progress);
+
+    match name.as_str() {
+        "help" | "-h" | "--help" => print_usage(&registry),
+        "lessons" => print_lessons(&registry, &ctx),
+        "reset" => {
+            ctx.progress_mut().reset()?;
+            println!("Progress has been reset.");
+        }
+        id => registry.run(id, &mut ctx)?,
+    }
+
+    Ok(())
+}
+
+fn print_usage(registry: &Registry) {
+    println!("usage: cpc <command> [args]");
+    println!();
+    println!("commands:");
+    println!("  lessons  List lessons in curriculum order with your progress");
+    println!("  reset    Forget which lessons have been completed");
+    println!();
+    println!("lessons:");
+
+    let width = registry
+        .lessons()
+        .map(|lesson| lesson.usage())
+        .chain(TOOLS.iter().map(|tool| tool.usage))
+        .flat_map(str::lines)
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+
+    for lesson in registry.lessons() {
+        print_entry(lesson.usage(), lesson.summary(), width);
+    }
+
+    println!();
+    println!("tools:");
+
+    for tool in TOOLS {
+        print_entry(tool.usage, tool.summary, width);
+    }
+}
+
+// A usage can list several forms, one per line; the summary goes next to
+// the first one.
+fn print_entry(usage: &str, summary: &str, width: usize) {
+    for (i, form) in usage.lines().enumerate() {
+        if i == 0 {
+            println!("  {form:width$}  {summary}");
+        } else {
+            println!("  {form}");
+        }
+    }
+}
+
+fn print_lessons(registry: &Registry, ctx: &Context) {
+    for (number, lesson) in registry.lessons().enumerate() {
+        let status = if ctx.progress().is_completed(lesson.id()) {
+            "done"
+        } else if registry
+            .missing_prerequisites(lesson, ctx.progress())
+            .is_empty()
+        {
+            "open"
+        } else {
+            "locked"
+        };
+
+        println!(
+            "{:2}. [{status:^6}] {} ({})",
+            number + 1,
+            lesson.title(),
+            lesson.id()
+        );
+        println!("    {}", lesson.summary());
+
+        if !lesson.prerequisites().is_empty() {
+            println!("    requires: {}", lesson.prerequisites().join(", "));
+        }
+    }
+}
@@ -1,16 +1,64 @@
+use std::io::{self, BufRead, Write};
+use std::panic;
+
+use crate::lessons::{Context, Lesson, LessonError};
+use crate::scalar::{parse_scalar, ScalarError};
+
+pub struct DataTypes;
+
+impl Lesson for DataTypes {
+    fn id(&self) -> &'static str {
+        "data-types"
+    }
+
+    fn title(&self) -> &'static str {
+        "Data Types"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Scalar and compound types"
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["variables"]
+    }
+
+    fn usage(&self) -> &'static str {
+        "data-types [--demonstrate-panic]"
+    }
+
+    // --demonstrate-panic indexes past the end of the array on purpose and
+    // shows the panic instead of asking for an index.
+    fn run(&self, ctx: &mut Context) -> Result<(), LessonError> {
+        let demonstrate_panic = match ctx.args() {
+            [] => false,
+            [flag] if flag == "--demonstrate-panic" => true,
+            _ => return Err(LessonError::usage(self.usage())),
+        };
+
+        run(demonstrate_panic);
+        Ok(())
+    }
+}
+
 /* Rust is a statically typed language, meaning it must know the types of all
 *  variables at compile time.
 *  The compiler can usually infer what type wanted to be used based on the value
 *  and how it is used.
 *  In cases when many types are possible, type annotation must be added.
 */
-fn main() {
+pub fn run(demonstrate_panic: bool) {
     // this won't compile as many types are possible.
     // let age = "27".parse().expect("Not a number!");
     let age: u8 = "27".parse().expect("Not a number!");
 
     println!("I am {age} years old.");
 
+    // expect only says that something went wrong; parse_scalar says what.
+    if let Err(err) = parse_scalar::<u8>("300") {
+        println!("Parsing 300 as a u8: {err}");
+    }
+
     // A scalar type represents a single value.
     // Rust has four primary scalar types: integers, floating-point numbers
     // Booleans, and characters.
@@ -103,6 +151,8 @@ fn main() {
     for c in chars {
         println!("{c}");
     }
+    // Each of these takes a different number of bytes once encoded.
+    println!("Run `cpc char zℤ🤗` to see how each one is stored.");
 
     /* Compound types can group multiple values into one type.
     *  Rust has two primitive compound types: tuples and arrays.
@@ -128,6 +178,7 @@ fn main() {
     println!("The value of y is: {x}");
     println!("The value of y is: {y}");
     println!("The value of y is: {z}");
+    println!("Try other patterns with `cpc let \"let (x, _, z) = (500, 6.4, 1u8)\"`.");
     // Element access can also be achieved using a period (.)
     println!("The value of x is: {0}", tup.0);
 
@@ -171,22 +222,25 @@ fn main() {
     // Invalid Array Element Access
     let a = [1, 2, 3, 4, 5];
 
-    println!("Please enter an array index.");
-
-    let mut index = String::new();
-
-    std::io::stdin()
-        .read_line(&mut index)
-        .expect("Failed to read line");
-
-    let index: usize = index
-        .trim()
-        .parse()
-        .expect("Index entered was not a number");
-
-    let element = a[index];
+    if demonstrate_panic {
+        // One past the end is enough, but 10 makes it obvious.
+        let index = 10;
+        println!("Indexing {a:?} with {index}:");
+
+        match index_or_panic(&a, index) {
+            Ok(element) => println!("The value of the element at index {index} is: {element}"),
+            Err(message) => println!("The program panicked: {message}"),
+        }
+    } else {
+        let stdin = std::io::stdin();
+        let found = index_prompt(stdin.lock(), std::io::stdout(), &a)
+            .expect("Failed to read line");
+
+        if let Some((index, element)) = found {
+            println!("The value of the element at index {index} is: {element}");
+        }
+    }
 
-    println!("The value of the element at index {index} is: {element}");
     // The program resulted in a runtime error at the point of using an invalid
     // value in the indexing operation.
     // The program exited with an error message and didn't execute the final
@@ -198,4 +252,63 @@ fn main() {
     // compiler can't possibly know what value a user will enter when they run
     // the code later.
     // Rust uses the term panicking when a program exits with an error.
+    // get() does the same check but returns None instead of panicking, which
+    // lets the program ask again.
+}
+
+// - Keeps asking until the index is inside the array. get() turns an index
+//   past the end into None, so it's handled like any other wrong answer.
+// - Returns None when the input runs out or the user types quit.
+pub fn index_prompt(
+    mut input: impl BufRead,
+    mut output: impl Write,
+    array: &[i32],
+) -> io::Result<Option<(usize, i32)>> {
+    loop {
+        writeln!(output, "Please enter an array index (or quit).")?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if matches!(line.trim().to_ascii_lowercase().as_str(), "quit" | "q" | "exit") {
+            return Ok(None);
+        }
+
+        let index = match parse_scalar::<usize>(&line) {
+            Ok(index) => index,
+            Err(ScalarError::Underflow { .. }) => {
+                writeln!(output, "An index can't be negative, the first element is at 0.")?;
+                continue;
+            }
+            Err(err) => {
+                writeln!(output, "{err}")?;
+                continue;
+            }
+        };
+
+        match array.get(index) {
+            Some(&element) => return Ok(Some((index, element))),
+            None => writeln!(
+                output,
+                "Index {index} is out of bounds, the array only has {} elements.",
+                array.len()
+            )?,
+        }
+    }
+}
+
+// - Indexes with [] like the book does, but inside catch_unwind, so the
+//   panic comes back as its message instead of ending the program.
+// - The default panic hook still runs and prints the usual "thread 'main'
+//   panicked at" report to stderr first. The hook is global, and swapping
+//   it out here would also silence panics on every other thread.
+pub fn index_or_panic(array: &[i32], index: usize) -> Result<i32, String> {
+    panic::catch_unwind(|| array[index]).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| String::from("the panic had no message"))
+    })
 }
@@ -0,0 +1,324 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+use crate::bignum::BigUint;
+
+// - An exact decimal number: digits / 10^scale, with a separate sign.
+// - Every finite float is a whole number times a power of two, and
+//   1 / 2^k = 5^k / 10^k, so every float can be written out exactly; the
+//   same goes for sums, differences and products of them.
+// - Values are kept without trailing zeros after the point, so equal values
+//   compare equal.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Decimal {
+    negative: bool,
+    digits: BigUint,
+    scale: u32,
+}
+
+// Exponents further out than this are refused when parsing, they would
+// need more digits than anyone wants to read.
+const MAX_EXPONENT: i64 = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseDecimalError {
+    Empty,
+    Invalid(String),
+    ExponentTooLarge(String),
+}
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseDecimalError::Empty => f.write_str("expected a number, found nothing"),
+            ParseDecimalError::Invalid(text) => write!(f, "`{text}` is not a decimal number"),
+            ParseDecimalError::ExponentTooLarge(text) => {
+                write!(f, "the exponent of `{text}` is too large")
+            }
+        }
+    }
+}
+
+impl Error for ParseDecimalError {}
+
+impl Decimal {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    fn new(negative: bool, digits: BigUint, scale: u32) -> Self {
+        let mut value = Self {
+            negative,
+            digits,
+            scale,
+        };
+        value.normalize();
+        value
+    }
+
+    // The exact value of a float, None for infinities and NaN.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        let bits = value.to_bits();
+        let negative = bits >> 63 == 1;
+        let stored_exponent = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = bits & ((1 << 52) - 1);
+
+        // value = mantissa * 2^exponent
+        let (mantissa, exponent) = match stored_exponent {
+            0 => (fraction, -1074),
+            _ => (fraction | 1 << 52, stored_exponent - 1075),
+        };
+
+        let value = if exponent >= 0 {
+            let digits = &BigUint::from(mantissa) * &BigUint::pow(2, exponent as u32);
+            Self::new(negative, digits, 0)
+        } else {
+            let scale = exponent.unsigned_abs();
+            let digits = &BigUint::from(mantissa) * &BigUint::pow(5, scale);
+            Self::new(negative, digits, scale)
+        };
+
+        Some(value)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative && !self.is_zero()
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            negative: false,
+            ..self.clone()
+        }
+    }
+
+    // The nearest f64, which is as close as an f64 can get to the value.
+    pub fn to_f64(&self) -> f64 {
+        self.to_string()
+            .parse()
+            .expect("a decimal is always a valid float")
+    }
+
+    // - The value as mantissa * 10^exponent, None for zero.
+    // - Unlike to_f64 this never underflows: the exponent is counted from
+    //   the digits, and only the leading ones go into the f64 mantissa.
+    pub fn to_scientific(&self) -> Option<Scientific> {
+        if self.is_zero() {
+            return None;
+        }
+
+        let digits = self.digits.to_string();
+        let exponent = digits.len() as i32 - 1 - self.scale as i32;
+        let leading = &digits[..digits.len().min(17)];
+        let mantissa: f64 = format!("{}.{}", &leading[..1], &leading[1..])
+            .parse()
+            .expect("leading digits are always a valid float");
+        let mantissa = if self.negative { -mantissa } else { mantissa };
+
+        Some(Scientific::new(mantissa, exponent))
+    }
+
+    // The digits scaled up to `scale` places after the point.
+    fn digits_at(&self, scale: u32) -> BigUint {
+        &self.digits * &BigUint::pow(10, scale - self.scale)
+    }
+
+    fn normalize(&mut self) {
+        while self.scale > 0 && !self.digits.is_zero() && self.digits.rem_u64(10) == 0 {
+            self.digits.div_rem_u64(10);
+            self.scale -= 1;
+        }
+
+        if self.digits.is_zero() {
+            self.negative = false;
+            self.scale = 0;
+        }
+    }
+}
+
+// - A nonzero number as mantissa * 10^exponent, with 1 <= |mantissa| < 10.
+// - Only meant for display: the mantissa is rounded to an f64, but the
+//   exponent can go far below where an f64 underflows to 0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scientific {
+    pub mantissa: f64,
+    pub exponent: i32,
+}
+
+impl Scientific {
+    fn new(mut mantissa: f64, mut exponent: i32) -> Self {
+        // Rounding the leading digits can carry into a new one, 9.99.. to 10.
+        while mantissa.abs() >= 10.0 {
+            mantissa /= 10.0;
+            exponent += 1;
+        }
+        while mantissa.abs() < 1.0 {
+            mantissa *= 10.0;
+            exponent -= 1;
+        }
+        Self { mantissa, exponent }
+    }
+
+    // The nearest f64, 0 or infinite when the exponent is out of its range.
+    pub fn to_f64(self) -> f64 {
+        format!("{}e{}", self.mantissa, self.exponent)
+            .parse()
+            .expect("a mantissa and an exponent are always a valid float")
+    }
+}
+
+impl Div for Scientific {
+    type Output = Scientific;
+
+    fn div(self, other: Scientific) -> Scientific {
+        Scientific::new(
+            self.mantissa / other.mantissa,
+            self.exponent - other.exponent,
+        )
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = ParseDecimalError;
+
+    // [-]digits[.digits][e[-]digits], with _ allowed between digits the way
+    // Rust float literals allow it.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        if text.is_empty() {
+            return Err(ParseDecimalError::Empty);
+        }
+        let invalid = || ParseDecimalError::Invalid(text.to_string());
+
+        let cleaned = text.replace('_', "");
+        let (negative, unsigned) = match cleaned.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, cleaned.strip_prefix('+').unwrap_or(&cleaned)),
+        };
+
+        let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+            Some((mantissa, exponent)) => {
+                let exponent: i64 = exponent.parse().map_err(|_| invalid())?;
+                (mantissa, exponent)
+            }
+            None => (unsigned, 0),
+        };
+        if exponent.abs() > MAX_EXPONENT {
+            return Err(ParseDecimalError::ExponentTooLarge(text.to_string()));
+        }
+
+        let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let all_digits = format!("{whole}{fraction}");
+        if all_digits.is_empty() || !all_digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let digits: BigUint = all_digits.parse().map_err(|_| invalid())?;
+        let scale = fraction.len() as i64 - exponent;
+
+        Ok(if scale >= 0 {
+            Self::new(negative, digits, scale as u32)
+        } else {
+            let digits = &digits * &BigUint::pow(10, scale.unsigned_abs() as u32);
+            Self::new(negative, digits, 0)
+        })
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (negative, _) => {
+                let scale = self.scale.max(other.scale);
+                let magnitude = self.digits_at(scale).cmp(&other.digits_at(scale));
+                if negative {
+                    magnitude.reverse()
+                } else {
+                    magnitude
+                }
+            }
+        }
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Self::new(!self.negative, self.digits, self.scale)
+    }
+}
+
+impl Add<&Decimal> for &Decimal {
+    type Output = Decimal;
+
+    fn add(self, other: &Decimal) -> Decimal {
+        let scale = self.scale.max(other.scale);
+        let (x, y) = (self.digits_at(scale), other.digits_at(scale));
+
+        if self.negative == other.negative {
+            return Decimal::new(self.negative, &x + &y, scale);
+        }
+
+        // Opposite signs: the larger magnitude decides the sign.
+        match x.checked_sub(&y) {
+            Some(difference) => Decimal::new(self.negative, difference, scale),
+            None => Decimal::new(other.negative, &y - &x, scale),
+        }
+    }
+}
+
+impl Sub<&Decimal> for &Decimal {
+    type Output = Decimal;
+
+    fn sub(self, other: &Decimal) -> Decimal {
+        self + &-other.clone()
+    }
+}
+
+impl Mul<&Decimal> for &Decimal {
+    type Output = Decimal;
+
+    fn mul(self, other: &Decimal) -> Decimal {
+        Decimal::new(
+            self.negative != other.negative,
+            &self.digits * &other.digits,
+            self.scale + other.scale,
+        )
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.digits.to_string();
+        let scale = self.scale as usize;
+        let sign = if self.is_negative() { "-" } else { "" };
+
+        if scale == 0 {
+            return write!(f, "{sign}{digits}");
+        }
+
+        let padded = format!("{digits:0>width$}", width = scale + 1);
+        let (whole, fraction) = padded.split_at(padded.len() - scale);
+        write!(f, "{sign}{whole}.{fraction}")
+    }
+}
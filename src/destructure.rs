@@ -0,0 +1,865 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::cli::ToolResult;
+use crate::eval::{self, EvalError, Ty};
+
+// - Runs a single `let` statement the way data_types.rs uses them:
+//     let (x, _, z): (i32, f64, u8) = (500, 6.4, 1);
+//     let [first, .., last] = [3; 5];
+// - Values are tuples, arrays, bool and char literals and anything `cpc
+//   eval` understands. Types are worked out like the compiler would,
+//   including the annotation, and the pattern has to match them exactly.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Scalar(Ty),
+    Bool,
+    Char,
+    Tuple(Vec<Type>),
+    Array(Box<Type>, usize),
+    // The element type of `[]`, written `_` like the compiler does.
+    Unknown,
+}
+
+impl Type {
+    fn unify(&self, other: &Type) -> Option<Type> {
+        match (self, other) {
+            (Type::Unknown, ty) | (ty, Type::Unknown) => Some(ty.clone()),
+            (Type::Scalar(a), Type::Scalar(b)) => a.unify(*b).map(Type::Scalar),
+            (Type::Bool, Type::Bool) => Some(Type::Bool),
+            (Type::Char, Type::Char) => Some(Type::Char),
+            (Type::Tuple(a), Type::Tuple(b)) if a.len() == b.len() => a
+                .iter()
+                .zip(b)
+                .map(|(a, b)| a.unify(b))
+                .collect::<Option<_>>()
+                .map(Type::Tuple),
+            (Type::Array(a, n), Type::Array(b, m)) if n == m => {
+                a.unify(b).map(|ty| Type::Array(Box::new(ty), *n))
+            }
+            _ => None,
+        }
+    }
+
+    fn or_default(&self) -> Type {
+        match self {
+            Type::Scalar(ty) => Type::Scalar(ty.or_default()),
+            Type::Tuple(types) => Type::Tuple(types.iter().map(Type::or_default).collect()),
+            Type::Array(ty, n) => Type::Array(Box::new(ty.or_default()), *n),
+            ty => ty.clone(),
+        }
+    }
+
+    fn is_known(&self) -> bool {
+        match self {
+            Type::Unknown => false,
+            Type::Tuple(types) => types.iter().all(Type::is_known),
+            Type::Array(ty, _) => ty.is_known(),
+            _ => true,
+        }
+    }
+
+    // - What size_of and align_of give on a 64-bit target. Primitives are
+    //   aligned to their own size.
+    // - Rust is free to reorder tuple fields and does so to avoid padding
+    //   between them, which leaves only the padding at the end that rounds
+    //   the size up to the alignment.
+    // - None when the size doesn't fit in a usize, which only a huge array
+    //   annotation can get to.
+    pub fn size(&self) -> Option<usize> {
+        match self {
+            Type::Tuple(types) => types
+                .iter()
+                .try_fold(0usize, |total, ty| total.checked_add(ty.size()?))?
+                .checked_next_multiple_of(self.align()),
+            Type::Array(ty, n) => ty.size()?.checked_mul(*n),
+            ty => Some(ty.align()),
+        }
+    }
+
+    pub fn align(&self) -> usize {
+        match self {
+            Type::Scalar(Ty::Int(ty)) => ty.bits() as usize / 8,
+            Type::Scalar(Ty::Float(ty)) => ty.bits() as usize / 8,
+            Type::Scalar(_) | Type::Unknown => 1,
+            Type::Bool => 1,
+            Type::Char => 4,
+            Type::Tuple(types) => types.iter().map(Type::align).max().unwrap_or(1),
+            Type::Array(ty, _) => ty.align(),
+        }
+    }
+
+    // The bytes that hold values, without any padding.
+    fn data_size(&self) -> Option<usize> {
+        match self {
+            Type::Tuple(types) => types
+                .iter()
+                .try_fold(0usize, |total, ty| total.checked_add(ty.data_size()?)),
+            Type::Array(ty, n) => ty.data_size()?.checked_mul(*n),
+            ty => ty.size(),
+        }
+    }
+
+    // How many bools, chars and numbers a value of this type is made of.
+    fn scalars(&self) -> Option<usize> {
+        match self {
+            Type::Tuple(types) => types
+                .iter()
+                .try_fold(0usize, |total, ty| total.checked_add(ty.scalars()?)),
+            Type::Array(ty, n) => ty.scalars()?.checked_mul(*n),
+            _ => Some(1),
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Scalar(ty) => ty.fmt(f),
+            Type::Bool => f.write_str("bool"),
+            Type::Char => f.write_str("char"),
+            Type::Tuple(types) => write_list(f, "(", types, ")"),
+            Type::Array(ty, n) => write!(f, "[{ty}; {n}]"),
+            Type::Unknown => f.write_str("_"),
+        }
+    }
+}
+
+// (a, b), with the trailing comma that tells (a,) apart from (a).
+fn write_list<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    open: &str,
+    items: &[T],
+    close: &str,
+) -> fmt::Result {
+    f.write_str(open)?;
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        item.fmt(f)?;
+    }
+    if items.len() == 1 && open == "(" {
+        f.write_str(",")?;
+    }
+    f.write_str(close)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Scalar(eval::Value),
+    Bool(bool),
+    Char(char),
+    Tuple(Vec<Value>),
+    Array(Vec<Value>),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Scalar(value) => value.fmt(f),
+            Value::Bool(value) => value.fmt(f),
+            Value::Char(value) => write!(f, "{value:?}"),
+            Value::Tuple(values) => write_list(f, "(", values, ")"),
+            Value::Array(values) => write_list(f, "[", values, "]"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LetError {
+    Syntax(String),
+    Eval(EvalError),
+    MismatchedTypes {
+        expected: Type,
+        found: String,
+    },
+    NotAnArray(Type),
+    TupleArity {
+        expected: usize,
+        found: usize,
+    },
+    ArrayLength {
+        required: usize,
+        length: usize,
+        rest: bool,
+    },
+    MultipleRest(&'static str),
+    RestBindingInTuple(String),
+    RestNotAllowed,
+    DuplicateBinding(String),
+    AnnotationsNeeded(Type),
+    ArrayTooLong(usize),
+    TooLarge(Type),
+}
+
+// - Every element of an array is built and printed, so [0u64; 1 << 60]
+//   would run out of memory long before it finished.
+// - The limit counts the scalars in the whole value, [[0; 300]; 300] is
+//   over it as well.
+pub const MAX_SCALARS: usize = 1 << 16;
+
+impl fmt::Display for LetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LetError::Syntax(message) => f.write_str(message),
+            LetError::Eval(err) => err.fmt(f),
+            LetError::MismatchedTypes { expected, found } => {
+                write!(
+                    f,
+                    "mismatched types: expected `{expected}`, found {found} (E0308)"
+                )
+            }
+            LetError::NotAnArray(ty) => {
+                write!(f, "expected an array or slice, found `{ty}` (E0529)")
+            }
+            LetError::TupleArity { expected, found } => write!(
+                f,
+                "mismatched types: expected a tuple with {expected} elements, \
+                 found one with {found} elements (E0308)"
+            ),
+            LetError::ArrayLength {
+                required,
+                length,
+                rest: false,
+            } => write!(
+                f,
+                "pattern requires {required} elements but array has {length} (E0527)"
+            ),
+            LetError::ArrayLength {
+                required,
+                length,
+                rest: true,
+            } => write!(
+                f,
+                "pattern requires at least {required} elements but array has {length} (E0528)"
+            ),
+            LetError::MultipleRest(kind) => {
+                write!(f, "`..` can only be used once per {kind} pattern")
+            }
+            LetError::RestBindingInTuple(name) => {
+                write!(f, "`{name} @` is not allowed in a tuple")
+            }
+            LetError::RestNotAllowed => f.write_str("`..` patterns are not allowed here"),
+            LetError::DuplicateBinding(name) => write!(
+                f,
+                "identifier `{name}` is bound more than once in the same pattern (E0416)"
+            ),
+            LetError::AnnotationsNeeded(ty) => {
+                write!(f, "type annotations needed for `{ty}` (E0282)")
+            }
+            LetError::ArrayTooLong(length) => write!(
+                f,
+                "an array of {length} elements is too long, at most {MAX_SCALARS} are allowed"
+            ),
+            LetError::TooLarge(ty) => write!(
+                f,
+                "`{ty}` is too large, at most {MAX_SCALARS} values are allowed in total"
+            ),
+        }
+    }
+}
+
+impl Error for LetError {}
+
+impl From<EvalError> for LetError {
+    fn from(err: EvalError) -> Self {
+        LetError::Eval(err)
+    }
+}
+
+fn syntax(message: String) -> LetError {
+    LetError::Syntax(message)
+}
+
+// - Calls `visit` with every character outside of char literals and how
+//   deeply it's nested in brackets, not counting itself.
+// - Every splitting step below goes through this, so a comma inside
+//   (1, 2) never splits the outer list.
+fn scan(text: &str, mut visit: impl FnMut(usize, char, usize)) {
+    let mut depth = 0usize;
+    let mut chars = text.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' => {
+                // Skip to the closing quote, past any escaped one.
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '\'' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '(' | '[' => {
+                visit(i, c, depth);
+                depth += 1;
+            }
+            ')' | ']' => {
+                depth = depth.saturating_sub(1);
+                visit(i, c, depth);
+            }
+            _ => visit(i, c, depth),
+        }
+    }
+}
+
+// Where `c` appears outside of any brackets.
+fn top_level(text: &str, c: char) -> Vec<usize> {
+    let mut found = Vec::new();
+    scan(text, |i, ch, depth| {
+        if ch == c && depth == 0 {
+            found.push(i);
+        }
+    });
+    found
+}
+
+fn split(text: &str, c: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for i in top_level(text, c) {
+        parts.push(&text[start..i]);
+        start = i + c.len_utf8();
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+// The inside of (..) or [..] when the brackets wrap all of `text`, which
+// they don't in (1) + (2).
+fn enclosed(text: &str, open: char, close: char) -> Option<&str> {
+    let inner = text.strip_prefix(open)?.strip_suffix(close)?;
+    let last = text.len() - close.len_utf8();
+
+    let mut wraps = true;
+    scan(text, |i, c, depth| {
+        if matches!(c, ')' | ']') && depth == 0 && i != last {
+            wraps = false;
+        }
+    });
+    wraps.then_some(inner)
+}
+
+// The items of a comma-separated list, and whether it ended with a comma.
+fn items(inner: &str) -> Result<(Vec<&str>, bool), LetError> {
+    if inner.trim().is_empty() {
+        return Ok((Vec::new(), false));
+    }
+
+    let mut items: Vec<&str> = split(inner, ',').into_iter().map(str::trim).collect();
+    let trailing = items.len() > 1 && items.last() == Some(&"");
+    if trailing {
+        items.pop();
+    }
+    if items.iter().any(|item| item.is_empty()) {
+        return Err(syntax(String::from("expected an element, found `,`")));
+    }
+    Ok((items, trailing))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Scalar(String),
+    Bool(bool),
+    Char(char),
+    Tuple(Vec<Expr>),
+    Array(Vec<Expr>),
+    Repeat(Box<Expr>, usize),
+}
+
+fn parse_char(text: &str) -> Option<char> {
+    let inner = text.strip_prefix('\'')?.strip_suffix('\'')?;
+    let mut chars = inner.chars();
+    let c = match (chars.next()?, chars.next()) {
+        (c, None) if c != '\\' => return Some(c),
+        ('\\', Some(escape)) => escape,
+        _ => return None,
+    };
+
+    let escaped = match c {
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        '0' => '\0',
+        '\\' | '\'' | '"' => c,
+        'u' => {
+            let hex = chars.as_str().strip_prefix('{')?.strip_suffix('}')?;
+            return u32::from_str_radix(hex, 16).ok().and_then(char::from_u32);
+        }
+        _ => return None,
+    };
+    chars.next().is_none().then_some(escaped)
+}
+
+fn parse_expr(text: &str) -> Result<Expr, LetError> {
+    let text = text.trim();
+
+    if let Some(inner) = enclosed(text, '[', ']') {
+        let parts = split(inner, ';');
+        if let [element, count] = parts[..] {
+            let count = count.trim().parse().map_err(|_| {
+                syntax(format!(
+                    "expected the array length, found `{}`",
+                    count.trim()
+                ))
+            })?;
+            if count > MAX_SCALARS {
+                return Err(LetError::ArrayTooLong(count));
+            }
+            return Ok(Expr::Repeat(Box::new(parse_expr(element)?), count));
+        }
+
+        let (elements, _) = items(inner)?;
+        let elements = elements.into_iter().map(parse_expr);
+        return Ok(Expr::Array(elements.collect::<Result<_, _>>()?));
+    }
+
+    if let Some(inner) = enclosed(text, '(', ')') {
+        let (elements, trailing) = items(inner)?;
+        // (5) is just 5 in brackets, (5,) is a tuple.
+        if elements.len() == 1 && !trailing {
+            return parse_expr(elements[0]);
+        }
+        let elements = elements.into_iter().map(parse_expr);
+        return Ok(Expr::Tuple(elements.collect::<Result<_, _>>()?));
+    }
+
+    match text {
+        "" => Err(syntax(String::from(
+            "expected an expression, found nothing",
+        ))),
+        "true" => Ok(Expr::Bool(true)),
+        "false" => Ok(Expr::Bool(false)),
+        _ if text.starts_with('\'') => parse_char(text)
+            .map(Expr::Char)
+            .ok_or_else(|| syntax(format!("`{text}` is not a valid char literal"))),
+        _ => Ok(Expr::Scalar(text.to_string())),
+    }
+}
+
+fn type_of(expr: &Expr) -> Result<Type, LetError> {
+    Ok(match expr {
+        Expr::Scalar(text) => Type::Scalar(eval::type_of(text)?),
+        Expr::Bool(_) => Type::Bool,
+        Expr::Char(_) => Type::Char,
+        Expr::Tuple(elements) => {
+            Type::Tuple(elements.iter().map(type_of).collect::<Result<_, _>>()?)
+        }
+        Expr::Array(elements) => {
+            let mut ty = Type::Unknown;
+            for element in elements {
+                let found = type_of(element)?;
+                ty = ty.unify(&found).ok_or_else(|| LetError::MismatchedTypes {
+                    expected: ty.clone(),
+                    found: format!("`{found}`"),
+                })?;
+            }
+            Type::Array(Box::new(ty), elements.len())
+        }
+        Expr::Repeat(element, count) => Type::Array(Box::new(type_of(element)?), *count),
+    })
+}
+
+fn value(expr: &Expr, ty: &Type) -> Result<Value, LetError> {
+    Ok(match (expr, ty) {
+        (Expr::Scalar(text), Type::Scalar(ty)) => {
+            Value::Scalar(eval::evaluate_as(text, *ty)?.value)
+        }
+        (Expr::Bool(b), _) => Value::Bool(*b),
+        (Expr::Char(c), _) => Value::Char(*c),
+        (Expr::Tuple(elements), Type::Tuple(types)) => Value::Tuple(
+            elements
+                .iter()
+                .zip(types)
+                .map(|(element, ty)| value(element, ty))
+                .collect::<Result<_, _>>()?,
+        ),
+        (Expr::Array(elements), Type::Array(ty, _)) => Value::Array(
+            elements
+                .iter()
+                .map(|element| value(element, ty))
+                .collect::<Result<_, _>>()?,
+        ),
+        (Expr::Repeat(element, count), Type::Array(ty, _)) => {
+            Value::Array(vec![value(element, ty)?; *count])
+        }
+        _ => unreachable!("the value was type checked"),
+    })
+}
+
+fn parse_type(text: &str) -> Result<Type, LetError> {
+    let text = text.trim();
+
+    if let Some(inner) = enclosed(text, '[', ']') {
+        let parts = split(inner, ';');
+        let [element, length] = parts[..] else {
+            return Err(syntax(format!("expected `[type; length]`, found `{text}`")));
+        };
+        let length = length.trim().parse().map_err(|_| {
+            syntax(format!(
+                "expected the array length, found `{}`",
+                length.trim()
+            ))
+        })?;
+        return Ok(Type::Array(Box::new(parse_type(element)?), length));
+    }
+
+    if let Some(inner) = enclosed(text, '(', ')') {
+        let (types, trailing) = items(inner)?;
+        if types.len() == 1 && !trailing {
+            return parse_type(types[0]);
+        }
+        let types = types.into_iter().map(parse_type);
+        return Ok(Type::Tuple(types.collect::<Result<_, _>>()?));
+    }
+
+    match text {
+        "bool" => Ok(Type::Bool),
+        "char" => Ok(Type::Char),
+        "_" => Ok(Type::Unknown),
+        _ => {
+            if let Ok(ty) = text.parse() {
+                return Ok(Type::Scalar(Ty::Int(ty)));
+            }
+            if let Ok(ty) = text.parse() {
+                return Ok(Type::Scalar(Ty::Float(ty)));
+            }
+            Err(EvalError::UnknownType(text.to_string()).into())
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Pattern {
+    Binding { name: String, mutable: bool },
+    Wildcard,
+    // `..`, or `rest @ ..` in an array pattern.
+    Rest(Option<String>),
+    Tuple(Vec<Pattern>),
+    Array(Vec<Pattern>),
+}
+
+const KEYWORDS: [&str; 8] = ["as", "false", "fn", "let", "mut", "ref", "true", "type"];
+
+fn parse_name(text: &str) -> Result<String, LetError> {
+    let mut chars = text.chars();
+    let valid = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&text);
+
+    if valid {
+        Ok(text.to_string())
+    } else {
+        Err(syntax(format!("expected a pattern, found `{text}`")))
+    }
+}
+
+fn parse_pattern(text: &str) -> Result<Pattern, LetError> {
+    let text = text.trim();
+
+    if let Some(inner) = enclosed(text, '[', ']') {
+        let (patterns, _) = items(inner)?;
+        let patterns = patterns.into_iter().map(parse_pattern);
+        return Ok(Pattern::Array(patterns.collect::<Result<_, _>>()?));
+    }
+
+    if let Some(inner) = enclosed(text, '(', ')') {
+        let (patterns, trailing) = items(inner)?;
+        if patterns.len() == 1 && !trailing {
+            return parse_pattern(patterns[0]);
+        }
+        let patterns = patterns.into_iter().map(parse_pattern);
+        return Ok(Pattern::Tuple(patterns.collect::<Result<_, _>>()?));
+    }
+
+    if let Some((name, rest)) = text.split_once('@') {
+        if rest.trim() != ".." {
+            return Err(syntax(format!(
+                "expected `..` after `@`, found `{}`",
+                rest.trim()
+            )));
+        }
+        return Ok(Pattern::Rest(Some(parse_name(name.trim())?)));
+    }
+
+    match text {
+        "_" => Ok(Pattern::Wildcard),
+        ".." => Ok(Pattern::Rest(None)),
+        _ => match text.strip_prefix("mut ") {
+            Some(name) => Ok(Pattern::Binding {
+                name: parse_name(name.trim())?,
+                mutable: true,
+            }),
+            None => Ok(Pattern::Binding {
+                name: parse_name(text)?,
+                mutable: false,
+            }),
+        },
+    }
+}
+
+// A name the pattern binds, along with its type and value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    pub name: String,
+    pub mutable: bool,
+    pub ty: Type,
+    pub value: Value,
+}
+
+// The patterns before and after the `..`, if there is one.
+struct Parts<'a> {
+    before: &'a [Pattern],
+    rest: Option<&'a Pattern>,
+    after: &'a [Pattern],
+}
+
+fn parts<'a>(patterns: &'a [Pattern], kind: &'static str) -> Result<Parts<'a>, LetError> {
+    let mut rests = patterns
+        .iter()
+        .enumerate()
+        .filter(|(_, pattern)| matches!(pattern, Pattern::Rest(_)));
+
+    match (rests.next(), rests.next()) {
+        (Some(_), Some(_)) => Err(LetError::MultipleRest(kind)),
+        (Some((i, rest)), None) => Ok(Parts {
+            before: &patterns[..i],
+            rest: Some(rest),
+            after: &patterns[i + 1..],
+        }),
+        (None, _) => Ok(Parts {
+            before: patterns,
+            rest: None,
+            after: &[],
+        }),
+    }
+}
+
+fn bind(
+    pattern: &Pattern,
+    ty: &Type,
+    value: &Value,
+    bindings: &mut Vec<Binding>,
+) -> Result<(), LetError> {
+    match (pattern, ty, value) {
+        (Pattern::Binding { name, mutable }, _, _) => {
+            if bindings.iter().any(|binding| &binding.name == name) {
+                return Err(LetError::DuplicateBinding(name.clone()));
+            }
+            bindings.push(Binding {
+                name: name.clone(),
+                mutable: *mutable,
+                ty: ty.clone(),
+                value: value.clone(),
+            });
+        }
+        (Pattern::Wildcard, _, _) => {}
+        (Pattern::Rest(_), _, _) => return Err(LetError::RestNotAllowed),
+        (Pattern::Tuple(patterns), Type::Tuple(types), Value::Tuple(values)) => {
+            let Parts {
+                before,
+                rest,
+                after,
+            } = parts(patterns, "tuple")?;
+            if let Some(Pattern::Rest(Some(name))) = rest {
+                return Err(LetError::RestBindingInTuple(name.clone()));
+            }
+
+            let length = types.len();
+            let fixed = before.len() + after.len();
+            if fixed > length || (rest.is_none() && fixed != length) {
+                return Err(LetError::TupleArity {
+                    expected: length,
+                    found: fixed,
+                });
+            }
+
+            for (i, pattern) in before.iter().enumerate() {
+                bind(pattern, &types[i], &values[i], bindings)?;
+            }
+            for (i, pattern) in (length - after.len()..).zip(after) {
+                bind(pattern, &types[i], &values[i], bindings)?;
+            }
+        }
+        (Pattern::Tuple(_), ty, _) => {
+            return Err(LetError::MismatchedTypes {
+                expected: ty.clone(),
+                found: String::from("tuple"),
+            })
+        }
+        (Pattern::Array(patterns), Type::Array(element, length), Value::Array(values)) => {
+            let Parts {
+                before,
+                rest,
+                after,
+            } = parts(patterns, "array")?;
+
+            let length = *length;
+            let fixed = before.len() + after.len();
+            if fixed > length || (rest.is_none() && fixed != length) {
+                return Err(LetError::ArrayLength {
+                    required: fixed,
+                    length,
+                    rest: rest.is_some(),
+                });
+            }
+
+            for (i, pattern) in before.iter().enumerate() {
+                bind(pattern, element, &values[i], bindings)?;
+            }
+            let end = length - after.len();
+            if let Some(Pattern::Rest(Some(name))) = rest {
+                let middle = Pattern::Binding {
+                    name: name.clone(),
+                    mutable: false,
+                };
+                let ty = Type::Array(element.clone(), end - before.len());
+                let value = Value::Array(values[before.len()..end].to_vec());
+                bind(&middle, &ty, &value, bindings)?;
+            }
+            for (i, pattern) in (end..).zip(after) {
+                bind(pattern, element, &values[i], bindings)?;
+            }
+        }
+        (Pattern::Array(_), ty, _) => return Err(LetError::NotAnArray(ty.clone())),
+    }
+
+    Ok(())
+}
+
+// The outcome of a `let`: the value on the right and what the pattern bound.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Let {
+    pub ty: Type,
+    pub value: Value,
+    pub bindings: Vec<Binding>,
+}
+
+// Runs `let <pattern>[: <type>] = <value>;`, the `let` and the `;` are
+// optional.
+pub fn run_let(text: &str) -> Result<Let, LetError> {
+    let text = text.trim();
+    let text = text.strip_suffix(';').unwrap_or(text).trim();
+    let text = text
+        .strip_prefix("let")
+        .filter(|rest| rest.starts_with(char::is_whitespace) || rest.starts_with(['(', '[']))
+        .unwrap_or(text);
+
+    let Some(&equals) = top_level(text, '=').first() else {
+        return Err(syntax(String::from("expected `=` followed by a value")));
+    };
+    let (lhs, rhs) = (&text[..equals], &text[equals + 1..]);
+
+    let (pattern, annotation) = match top_level(lhs, ':').first() {
+        Some(&colon) => (&lhs[..colon], Some(parse_type(&lhs[colon + 1..])?)),
+        None => (lhs, None),
+    };
+    let pattern = parse_pattern(pattern)?;
+    let expr = parse_expr(rhs)?;
+
+    let found = type_of(&expr)?;
+    let ty = match annotation {
+        Some(expected) => expected
+            .unify(&found)
+            .ok_or_else(|| LetError::MismatchedTypes {
+                expected,
+                found: format!("`{found}`"),
+            })?,
+        None => found,
+    }
+    .or_default();
+    if !ty.is_known() {
+        return Err(LetError::AnnotationsNeeded(ty));
+    }
+    if ty.scalars().is_none_or(|scalars| scalars > MAX_SCALARS) {
+        return Err(LetError::TooLarge(ty));
+    }
+
+    let value = value(&expr, &ty)?;
+    let mut bindings = Vec::new();
+    bind(&pattern, &ty, &value, &mut bindings)?;
+
+    Ok(Let {
+        ty,
+        value,
+        bindings,
+    })
+}
+
+// run_let rejects anything near large enough to overflow, so the fallback
+// is never printed.
+fn describe_size(ty: &Type) -> String {
+    let (Some(size), Some(data_size)) = (ty.size(), ty.data_size()) else {
+        return String::from("too large to measure");
+    };
+    let unit = if size == 1 { "byte" } else { "bytes" };
+    let padding = size - data_size;
+    if padding > 0 {
+        format!("{size} {unit} ({padding} of them padding)")
+    } else {
+        format!("{size} {unit}")
+    }
+}
+
+// - cpc let "let (x, _, z) = (500, 6.4, 1u8)" lists x and z with their
+//   types, values and sizes.
+// - Patterns that don't fit the value are rejected with the compiler's
+//   error.
+pub fn let_command(args: &[String]) -> ToolResult {
+    if args.is_empty() {
+        return Err("usage: cpc let \"let <pattern> = <value>\"".into());
+    }
+    let text = args.join(" ");
+    let result = run_let(&text)?;
+
+    println!("{}", text.trim());
+    println!("  value: {}", result.value);
+    println!(
+        "  type:  {}, {}, aligned to {}",
+        result.ty,
+        describe_size(&result.ty),
+        result.ty.align()
+    );
+
+    if result.bindings.is_empty() {
+        println!("  nothing is bound, every part is ignored");
+        return Ok(());
+    }
+
+    let rows: Vec<[String; 4]> = result
+        .bindings
+        .iter()
+        .map(|binding| {
+            let name = if binding.mutable {
+                format!("mut {}", binding.name)
+            } else {
+                binding.name.clone()
+            };
+            [
+                name,
+                binding.ty.to_string(),
+                binding.value.to_string(),
+                describe_size(&binding.ty),
+            ]
+        })
+        .collect();
+    let width = |column: usize| {
+        rows.iter()
+            .map(|row| row[column].chars().count())
+            .max()
+            .unwrap_or(0)
+    };
+    let (name, ty, value) = (width(0), width(1), width(2));
+
+    println!();
+    for [n, t, v, size] in &rows {
+        println!("  {n:<name$}  {t:<ty$}  {v:<value$}  {size}");
+    }
+
+    Ok(())
+}
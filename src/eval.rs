@@ -0,0 +1,766 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::cli::ToolResult;
+use crate::floats::{FloatError, FloatType, FloatValue};
+use crate::ints::{IntType, IntValue};
+use crate::literal::{parse_literal_as, LiteralError};
+use crate::overflow::{self, Outcome};
+
+// - Evaluates arithmetic the way a Rust program would: integer literals,
+//   float literals, unary -, + - * / %, parentheses and `as` casts.
+// - Every expression has a type. Unsuffixed literals take their type from
+//   whatever they're combined with and otherwise default to i32 and f64,
+//   and an operator never mixes two different types.
+// - Integer arithmetic is checked, so where a program would panic the
+//   evaluator reports an error instead.
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Int(String),
+    Float(String),
+    Op(BinOp),
+    Minus,
+    LParen,
+    RParen,
+    As,
+    Ident(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinOp {
+    pub fn symbol(self) -> char {
+        match self {
+            BinOp::Add => '+',
+            BinOp::Sub => '-',
+            BinOp::Mul => '*',
+            BinOp::Div => '/',
+            BinOp::Rem => '%',
+        }
+    }
+
+    fn int_op(self) -> overflow::Op {
+        match self {
+            BinOp::Add => overflow::Op::Add,
+            BinOp::Sub => overflow::Op::Sub,
+            BinOp::Mul => overflow::Op::Mul,
+            BinOp::Div => overflow::Op::Div,
+            BinOp::Rem => overflow::Op::Rem,
+        }
+    }
+
+    // The way the compiler words a type mismatch for each operator.
+    fn mismatch(self, lhs: Ty, rhs: Ty) -> String {
+        match self {
+            BinOp::Add => format!("cannot add `{rhs}` to `{lhs}`"),
+            BinOp::Sub => format!("cannot subtract `{rhs}` from `{lhs}`"),
+            BinOp::Mul => format!("cannot multiply `{lhs}` by `{rhs}`"),
+            BinOp::Div => format!("cannot divide `{lhs}` by `{rhs}`"),
+            BinOp::Rem => {
+                format!("cannot calculate the remainder of `{lhs}` divided by `{rhs}`")
+            }
+        }
+    }
+}
+
+// - The type of an expression while it's being checked. `{integer}` and
+//   `{float}` are unsuffixed literals whose type isn't known yet, written
+//   the way the compiler writes them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ty {
+    Int(IntType),
+    Float(FloatType),
+    IntVar,
+    FloatVar,
+}
+
+impl Ty {
+    // What an unsuffixed literal ends up as when nothing decides for it.
+    pub fn or_default(self) -> Ty {
+        match self {
+            Ty::IntVar => Ty::Int(IntType::I32),
+            Ty::FloatVar => Ty::Float(FloatType::F64),
+            ty => ty,
+        }
+    }
+
+    // The type both sides can have, None when they can't agree.
+    pub fn unify(self, other: Ty) -> Option<Ty> {
+        match (self, other) {
+            (a, b) if a == b => Some(a),
+            (Ty::IntVar, Ty::Int(ty)) | (Ty::Int(ty), Ty::IntVar) => Some(Ty::Int(ty)),
+            (Ty::FloatVar, Ty::Float(ty)) | (Ty::Float(ty), Ty::FloatVar) => Some(Ty::Float(ty)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ty::Int(ty) => ty.fmt(f),
+            Ty::Float(ty) => ty.fmt(f),
+            Ty::IntVar => f.write_str("{integer}"),
+            Ty::FloatVar => f.write_str("{float}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Int(String),
+    Float(String),
+    Neg(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    Cast(Box<Expr>, Ty),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    Int(IntValue),
+    Float(FloatValue),
+}
+
+impl Value {
+    pub fn ty(self) -> Ty {
+        match self {
+            Value::Int(value) => Ty::Int(value.ty()),
+            Value::Float(value) => Ty::Float(value.ty()),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(value) => value.fmt(f),
+            Value::Float(value) => value.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    UnexpectedChar(char),
+    Syntax(String),
+    UnknownType(String),
+    Literal(LiteralError),
+    Float(FloatError),
+    Mismatched { op: BinOp, lhs: Ty, rhs: Ty },
+    MismatchedTypes { expected: Ty, found: Ty },
+    NegateUnsigned(Ty),
+    FloatOutOfRange { literal: String, ty: FloatType },
+    Overflow(String),
+    Panic(String),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::UnexpectedChar(c) => write!(f, "unknown start of token: `{c}`"),
+            EvalError::Syntax(message) => f.write_str(message),
+            EvalError::UnknownType(name) => {
+                write!(f, "cannot find type `{name}` in this scope (E0412)")
+            }
+            EvalError::Literal(err) => err.fmt(f),
+            EvalError::Float(err) => err.fmt(f),
+            EvalError::Mismatched { op, lhs, rhs } => {
+                write!(f, "{} (E0277)", op.mismatch(*lhs, *rhs))
+            }
+            EvalError::MismatchedTypes { expected, found } => {
+                write!(
+                    f,
+                    "mismatched types: expected `{expected}`, found `{found}` (E0308)"
+                )
+            }
+            EvalError::NegateUnsigned(ty) => {
+                write!(f, "cannot apply unary operator `-` to type `{ty}` (E0600)")
+            }
+            EvalError::FloatOutOfRange { literal, ty } => {
+                write!(
+                    f,
+                    "literal out of range for `{ty}`: `{literal}` would be infinite"
+                )
+            }
+            EvalError::Overflow(message) => {
+                write!(f, "this arithmetic operation will overflow: {message}")
+            }
+            EvalError::Panic(message) => {
+                write!(f, "this operation will panic at runtime: {message}")
+            }
+        }
+    }
+}
+
+impl Error for EvalError {}
+
+impl From<LiteralError> for EvalError {
+    fn from(err: LiteralError) -> Self {
+        EvalError::Literal(err)
+    }
+}
+
+impl From<FloatError> for EvalError {
+    fn from(err: FloatError) -> Self {
+        EvalError::Float(err)
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, EvalError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        // b'A', with \' and \\ escapes kept inside the token.
+        if c == 'b' && chars.get(i + 1) == Some(&'\'') {
+            let start = i;
+            i += 2;
+            while i < chars.len() && chars[i] != '\'' {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            i += 1;
+            let end = i.min(chars.len());
+            tokens.push(Token::Int(chars[start..end].iter().collect()));
+            continue;
+        }
+
+        if c.is_ascii_digit() {
+            let start = i;
+            let radix_prefix = c == '0' && matches!(chars.get(i + 1), Some('x' | 'o' | 'b'));
+            let mut float = false;
+
+            while let Some(&c) = chars.get(i) {
+                let next = chars.get(i + 1).copied();
+                match c {
+                    '.' if !float && !radix_prefix && next.is_some_and(|n| n.is_ascii_digit()) => {
+                        float = true;
+                    }
+                    // 1. is a float too, as long as nothing that could be a
+                    // method or a field follows.
+                    '.' if !float
+                        && !radix_prefix
+                        && !next.is_some_and(|n| n.is_alphanumeric() || n == '_' || n == '.') =>
+                    {
+                        float = true;
+                    }
+                    'e' | 'E'
+                        if !radix_prefix
+                            && (next.is_some_and(|n| n.is_ascii_digit())
+                                || (matches!(next, Some('+' | '-'))
+                                    && chars.get(i + 2).is_some_and(|n| n.is_ascii_digit()))) =>
+                    {
+                        float = true;
+                        i += 1;
+                    }
+                    c if c.is_alphanumeric() || c == '_' => {}
+                    _ => break,
+                }
+                i += 1;
+            }
+
+            let literal: String = chars[start..i].iter().collect();
+            let float =
+                float || (!radix_prefix && (literal.ends_with("f32") || literal.ends_with("f64")));
+            tokens.push(if float {
+                Token::Float(literal)
+            } else {
+                Token::Int(literal)
+            });
+            continue;
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while chars
+                .get(i)
+                .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            tokens.push(if word == "as" {
+                Token::As
+            } else {
+                Token::Ident(word)
+            });
+            continue;
+        }
+
+        tokens.push(match c {
+            '+' => Token::Op(BinOp::Add),
+            '-' => Token::Minus,
+            '*' => Token::Op(BinOp::Mul),
+            '/' => Token::Op(BinOp::Div),
+            '%' => Token::Op(BinOp::Rem),
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            _ => return Err(EvalError::UnexpectedChar(c)),
+        });
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+// Recursive descent with Rust's precedence, tightest first:
+//   unary -, then as, then * / %, then + -
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expected(&self, what: &str) -> EvalError {
+        let found = match self.peek() {
+            Some(token) => describe(token),
+            None => String::from("end of input"),
+        };
+        EvalError::Syntax(format!("expected {what}, found {found}"))
+    }
+
+    fn expression(&mut self) -> Result<Expr, EvalError> {
+        let mut lhs = self.term()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Op(BinOp::Add)) => BinOp::Add,
+                Some(Token::Minus) => BinOp::Sub,
+                _ => return Ok(lhs),
+            };
+            self.position += 1;
+            let rhs = self.term()?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, EvalError> {
+        let mut lhs = self.cast()?;
+        while let Some(&Token::Op(op @ (BinOp::Mul | BinOp::Div | BinOp::Rem))) = self.peek() {
+            self.position += 1;
+            let rhs = self.cast()?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn cast(&mut self) -> Result<Expr, EvalError> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::As) {
+            self.position += 1;
+            let Some(Token::Ident(name)) = self.next() else {
+                self.position -= 1;
+                return Err(self.expected("a type after `as`"));
+            };
+            expr = Expr::Cast(Box::new(expr), parse_type(&name)?);
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, EvalError> {
+        if self.peek() == Some(&Token::Minus) {
+            self.position += 1;
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, EvalError> {
+        match self.peek().cloned() {
+            Some(Token::Int(text)) => {
+                self.position += 1;
+                Ok(Expr::Int(text))
+            }
+            Some(Token::Float(text)) => {
+                self.position += 1;
+                Ok(Expr::Float(text))
+            }
+            Some(Token::LParen) => {
+                self.position += 1;
+                let expr = self.expression()?;
+                if self.next() != Some(Token::RParen) {
+                    self.position -= 1;
+                    return Err(self.expected("`)`"));
+                }
+                Ok(expr)
+            }
+            _ => Err(self.expected("an expression")),
+        }
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Int(text) | Token::Float(text) => format!("`{text}`"),
+        Token::Op(op) => format!("`{}`", op.symbol()),
+        Token::Minus => String::from("`-`"),
+        Token::LParen => String::from("`(`"),
+        Token::RParen => String::from("`)`"),
+        Token::As => String::from("`as`"),
+        Token::Ident(name) => format!("`{name}`"),
+    }
+}
+
+fn parse_type(name: &str) -> Result<Ty, EvalError> {
+    if let Ok(ty) = name.parse() {
+        return Ok(Ty::Int(ty));
+    }
+    if let Ok(ty) = name.parse() {
+        return Ok(Ty::Float(ty));
+    }
+    Err(EvalError::UnknownType(name.to_string()))
+}
+
+fn parse(text: &str) -> Result<Expr, EvalError> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        position: 0,
+    };
+
+    let expr = parser.expression()?;
+    if parser.peek().is_some() {
+        return Err(parser.expected("an operator"));
+    }
+    Ok(expr)
+}
+
+// Works out the type of an expression, the way the compiler would before
+// anything is run.
+fn infer(expr: &Expr) -> Result<Ty, EvalError> {
+    match expr {
+        // Only the suffix matters here, the digits are checked once the type
+        // is known.
+        Expr::Int(text) if text.starts_with("b'") => Ok(Ty::Int(IntType::U8)),
+        Expr::Int(text) => Ok(IntType::ALL
+            .into_iter()
+            .find(|ty| text.ends_with(ty.name()))
+            .map_or(Ty::IntVar, Ty::Int)),
+        Expr::Float(text) => Ok(FloatType::ALL
+            .into_iter()
+            .find(|ty| text.ends_with(ty.name()))
+            .map_or(Ty::FloatVar, Ty::Float)),
+        Expr::Neg(inner) => match infer(inner)? {
+            ty @ Ty::Int(int) if !int.is_signed() => Err(EvalError::NegateUnsigned(ty)),
+            ty => Ok(ty),
+        },
+        Expr::Binary(op, lhs, rhs) => {
+            let (lhs, rhs) = (infer(lhs)?, infer(rhs)?);
+            lhs.unify(rhs)
+                .ok_or(EvalError::Mismatched { op: *op, lhs, rhs })
+        }
+        Expr::Cast(inner, ty) => {
+            cast_source(inner, *ty)?;
+            Ok(*ty)
+        }
+    }
+}
+
+// 5 and -5, the literals that take their type from what's around them.
+fn is_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Int(_) | Expr::Float(_) => true,
+        Expr::Neg(inner) => is_literal(inner),
+        _ => false,
+    }
+}
+
+// - The type of the expression being cast. Like rustc, an unsuffixed
+//   literal right under `as` takes the target type when it can, so 300 as
+//   u8 is an out of range u8 and -1 as u8 negates an unsigned type.
+// - Anything else, or an integer literal cast to a float, gets its own type
+//   and the defaults first: (200 + 100) as u8 is 300_i32 as u8.
+fn cast_source(inner: &Expr, target: Ty) -> Result<Ty, EvalError> {
+    let found = infer(inner)?;
+    if !is_literal(inner) {
+        return Ok(found);
+    }
+
+    match found.unify(target) {
+        Some(ty @ Ty::Int(int)) if !int.is_signed() && matches!(inner, Expr::Neg(_)) => {
+            Err(EvalError::NegateUnsigned(ty))
+        }
+        Some(ty) => Ok(ty),
+        None => Ok(found),
+    }
+}
+
+// - Runs a checked expression. `ty` is the type the whole expression was
+//   given, which is where unsuffixed literals get their type from.
+// - Notes explain the results that tend to surprise people.
+#[derive(Default)]
+struct Evaluator {
+    notes: Vec<String>,
+}
+
+impl Evaluator {
+    fn eval(&mut self, expr: &Expr, ty: Ty) -> Result<Value, EvalError> {
+        match (expr, ty) {
+            (Expr::Int(text), Ty::Int(ty)) => Ok(Value::Int(parse_literal_as(text, ty)?)),
+            (Expr::Float(text), Ty::Float(ty)) => {
+                let value = FloatValue::parse(text, ty)?;
+                if value.to_f64().is_infinite() {
+                    return Err(EvalError::FloatOutOfRange {
+                        literal: text.clone(),
+                        ty,
+                    });
+                }
+                Ok(Value::Float(value))
+            }
+            // -128i8 is fine even though 128i8 on its own is out of range.
+            (Expr::Neg(inner), Ty::Int(int)) if matches!(**inner, Expr::Int(_)) => {
+                let Expr::Int(text) = &**inner else {
+                    unreachable!()
+                };
+                if !int.is_signed() {
+                    return Err(EvalError::NegateUnsigned(ty));
+                }
+                Ok(Value::Int(parse_literal_as(&format!("-{text}"), int)?))
+            }
+            (Expr::Neg(inner), _) => match self.eval(inner, ty)? {
+                Value::Int(value) => {
+                    let zero = IntValue::from_bits(value.ty(), 0);
+                    match overflow::evaluate(zero, overflow::Op::Sub, value).checked {
+                        Outcome::Checked(Some(negated)) => Ok(Value::Int(negated)),
+                        _ => Err(EvalError::Overflow(format!(
+                            "attempt to negate `{value}_{}`, which would overflow",
+                            value.ty()
+                        ))),
+                    }
+                }
+                Value::Float(FloatValue::F32(value)) => Ok(Value::Float(FloatValue::F32(-value))),
+                Value::Float(FloatValue::F64(value)) => Ok(Value::Float(FloatValue::F64(-value))),
+            },
+            (Expr::Binary(op, lhs, rhs), _) => {
+                let lhs = self.eval(lhs, ty)?;
+                let rhs = self.eval(rhs, ty)?;
+                self.binary(*op, lhs, rhs)
+            }
+            (Expr::Cast(inner, target), _) => {
+                let inner_ty = cast_source(inner, *target)?;
+                if matches!(inner_ty, Ty::IntVar | Ty::FloatVar) {
+                    self.note_default(inner_ty);
+                }
+                let value = self.eval(inner, inner_ty.or_default())?;
+                Ok(cast(value, *target))
+            }
+            _ => unreachable!("the expression was type checked"),
+        }
+    }
+
+    fn binary(&mut self, op: BinOp, lhs: Value, rhs: Value) -> Result<Value, EvalError> {
+        match (lhs, rhs) {
+            (Value::Int(a), Value::Int(b)) => {
+                let results = overflow::evaluate(a, op.int_op(), b);
+                let ty = a.ty();
+
+                let value = match results.checked {
+                    Outcome::Checked(Some(value)) => value,
+                    _ => {
+                        let computation = format!("`{a}_{ty} {} {b}_{ty}`", op.symbol());
+                        return Err(match results.wrapping {
+                            Outcome::Panic(_) if op == BinOp::Div => EvalError::Panic(format!(
+                                "attempt to divide `{a}_{ty}` by zero"
+                            )),
+                            Outcome::Panic(_) => EvalError::Panic(format!(
+                                "attempt to calculate the remainder of `{a}_{ty}` with a divisor of zero"
+                            )),
+                            _ => EvalError::Overflow(format!(
+                                "attempt to compute {computation}, which would overflow"
+                            )),
+                        });
+                    }
+                };
+
+                self.note_division(op, a, b, value);
+                Ok(Value::Int(value))
+            }
+            (Value::Float(FloatValue::F32(a)), Value::Float(FloatValue::F32(b))) => {
+                Ok(Value::Float(FloatValue::F32(float_op(op, a, b))))
+            }
+            (Value::Float(FloatValue::F64(a)), Value::Float(FloatValue::F64(b))) => {
+                Ok(Value::Float(FloatValue::F64(float_op(op, a, b))))
+            }
+            _ => Err(EvalError::Mismatched {
+                op,
+                lhs: lhs.ty(),
+                rhs: rhs.ty(),
+            }),
+        }
+    }
+
+    fn note_default(&mut self, ty: Ty) {
+        let note = match ty {
+            Ty::IntVar => "integer literals without a suffix default to i32",
+            Ty::FloatVar => "float literals without a suffix default to f64",
+            _ => return,
+        };
+        if !self.notes.iter().any(|existing| existing == note) {
+            self.notes.push(note.to_string());
+        }
+    }
+
+    fn note_division(&mut self, op: BinOp, a: IntValue, b: IntValue, result: IntValue) {
+        let exact = a.to_string().parse::<f64>().unwrap_or(0.0)
+            / b.to_string().parse::<f64>().unwrap_or(1.0);
+
+        match op {
+            BinOp::Div if exact.fract() != 0.0 => self.notes.push(format!(
+                "integer division truncates toward zero: {a} / {b} is {exact:.3}, which becomes {result}"
+            )),
+            BinOp::Rem if a.to_string().starts_with('-') && result.to_bits() != 0 => {
+                self.notes.push(format!(
+                    "the remainder takes the sign of the dividend: {a} % {b} is {result}, \
+                     use rem_euclid for a result that is never negative"
+                ))
+            }
+            _ => {}
+        }
+    }
+}
+
+fn float_op<T>(op: BinOp, a: T, b: T) -> T
+where
+    T: std::ops::Add<Output = T>
+        + std::ops::Sub<Output = T>
+        + std::ops::Mul<Output = T>
+        + std::ops::Div<Output = T>
+        + std::ops::Rem<Output = T>,
+{
+    match op {
+        BinOp::Add => a + b,
+        BinOp::Sub => a - b,
+        BinOp::Mul => a * b,
+        BinOp::Div => a / b,
+        BinOp::Rem => a % b,
+    }
+}
+
+// The value widened to 128 bits, sign-extended for signed types, which is
+// what `as` starts from when it changes the width.
+fn wide_bits(value: IntValue) -> u128 {
+    let ty = value.ty();
+    let unused = 128 - ty.bits();
+    if ty.is_signed() {
+        (((value.to_bits() << unused) as i128) >> unused) as u128
+    } else {
+        value.to_bits()
+    }
+}
+
+// - Integer to integer keeps the low bits, so -1i32 as u8 is 255.
+// - Float to integer rounds toward zero and saturates, NaN becomes 0.
+// - Anything to a float rounds to the nearest value.
+fn cast(value: Value, target: Ty) -> Value {
+    match (value, target) {
+        (Value::Int(value), Ty::Int(ty)) => Value::Int(IntValue::from_bits(ty, wide_bits(value))),
+        (Value::Int(value), Ty::Float(ty)) => {
+            let bits = wide_bits(value);
+            let signed = value.ty().is_signed();
+            Value::Float(match ty {
+                FloatType::F32 if signed => FloatValue::F32(bits as i128 as f32),
+                FloatType::F32 => FloatValue::F32(bits as f32),
+                FloatType::F64 if signed => FloatValue::F64(bits as i128 as f64),
+                FloatType::F64 => FloatValue::F64(bits as f64),
+            })
+        }
+        (Value::Float(value), Ty::Int(ty)) => {
+            let float = value.to_f64();
+            let int = if ty.is_signed() {
+                IntValue::from_i128(ty, (float as i128).clamp(ty.min(), ty.max() as i128))
+            } else {
+                IntValue::from_u128(ty, (float as u128).min(ty.max()))
+            };
+            Value::Int(int.expect("the value was clamped to the type's range"))
+        }
+        (Value::Float(value), Ty::Float(FloatType::F32)) => {
+            Value::Float(FloatValue::F32(value.to_f64() as f32))
+        }
+        (Value::Float(value), Ty::Float(FloatType::F64)) => {
+            Value::Float(FloatValue::F64(value.to_f64()))
+        }
+        (_, Ty::IntVar | Ty::FloatVar) => unreachable!("casts always name a type"),
+    }
+}
+
+// The result of evaluating an expression, and notes on anything in it that
+// behaves differently from arithmetic on paper.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluation {
+    pub value: Value,
+    pub notes: Vec<String>,
+}
+
+pub fn evaluate(text: &str) -> Result<Evaluation, EvalError> {
+    let expr = parse(text)?;
+    let ty = infer(&expr)?;
+    run(&expr, ty)
+}
+
+// The type an expression has before anything decides its unsuffixed
+// literals, {integer} for `1 + 2`.
+pub fn type_of(text: &str) -> Result<Ty, EvalError> {
+    infer(&parse(text)?)
+}
+
+// Evaluates an expression that has to end up as `expected`, the way the
+// 200 in `let x: u8 = 200;` is a u8.
+pub fn evaluate_as(text: &str, expected: Ty) -> Result<Evaluation, EvalError> {
+    let expr = parse(text)?;
+    let found = infer(&expr)?;
+    let ty = found
+        .unify(expected)
+        .ok_or(EvalError::MismatchedTypes { expected, found })?;
+    run(&expr, ty)
+}
+
+fn run(expr: &Expr, ty: Ty) -> Result<Evaluation, EvalError> {
+    let mut evaluator = Evaluator::default();
+    evaluator.note_default(ty);
+    let value = evaluator.eval(expr, ty.or_default())?;
+
+    Ok(Evaluation {
+        value,
+        notes: evaluator.notes,
+    })
+}
+
+// cpc eval "-5 / 3" prints -1 along with its type, i32.
+pub fn eval_command(args: &[String]) -> ToolResult {
+    if args.is_empty() {
+        return Err("usage: cpc eval <expression>".into());
+    }
+
+    let text = args.join(" ");
+    let evaluation = evaluate(&text)?;
+
+    println!("{text} = {} ({})", evaluation.value, evaluation.value.ty());
+    for note in evaluation.notes {
+        println!("note: {note}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,281 @@
+use std::fmt::Write;
+
+use crate::fibonnacci::{fibonnacci, fibonnacci_big_with, BigFibonacci, FibAlgorithm};
+use crate::lessons::{Context, Lesson, LessonError};
+use crate::rational::Rational;
+use crate::temperature::{table, Scale, Temperature, TemperatureError};
+
+// The exercises at the end of chapter 3, each one a lesson of its own:
+// - twelve-days prints the lyrics of "The Twelve Days of Christmas", taking
+//   advantage of the repetition in the song.
+// - nth-fibonacci generates the nth Fibonnacci number.
+// - temperatures converts temperatures between Fahrenheit and Celsius.
+// Each lesson prints a string built by a pub function, so the text can be
+// compared against the expected output in tests/golden.
+
+pub struct TwelveDays;
+
+impl Lesson for TwelveDays {
+    fn id(&self) -> &'static str {
+        "twelve-days"
+    }
+
+    fn title(&self) -> &'static str {
+        "The Twelve Days of Christmas"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Sing a cumulative song with loops over arrays"
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["control-flow"]
+    }
+
+    fn usage(&self) -> &'static str {
+        "twelve-days [days]"
+    }
+
+    fn run(&self, ctx: &mut Context) -> Result<(), LessonError> {
+        let days = match ctx.args() {
+            [] => DAYS.len(),
+            [days] => match days.parse() {
+                Ok(days @ 1..=12) => days,
+                _ => {
+                    return Err(LessonError::InvalidArgs(format!(
+                        "`{days}` is not a day of Christmas, pick one from 1 to 12"
+                    )))
+                }
+            },
+            _ => return Err(LessonError::usage(self.usage())),
+        };
+        print!("{}", lyrics(days));
+        Ok(())
+    }
+}
+
+const DAYS: [&str; 12] = [
+    "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth", "tenth",
+    "eleventh", "twelfth",
+];
+
+// The gift of each day, the partridge is handled on its own since it's the
+// only one whose line changes.
+const GIFTS: [&str; 12] = [
+    "a partridge in a pear tree",
+    "Two turtle doves",
+    "Three French hens",
+    "Four calling birds",
+    "Five golden rings",
+    "Six geese a-laying",
+    "Seven swans a-swimming",
+    "Eight maids a-milking",
+    "Nine ladies dancing",
+    "Ten lords a-leaping",
+    "Eleven pipers piping",
+    "Twelve drummers drumming",
+];
+
+// - The first `days` verses, separated by blank lines.
+// - Every verse counts the gifts back down from the newest one, so verse n
+//   repeats all of verse n - 1 after its own gift.
+// - Panics if `days` is more than 12.
+pub fn lyrics(days: usize) -> String {
+    let mut song = String::new();
+
+    for (day, ordinal) in DAYS[..days].iter().enumerate() {
+        if day > 0 {
+            song.push('\n');
+        }
+        writeln!(
+            song,
+            "On the {ordinal} day of Christmas my true love sent to me"
+        )
+        .unwrap();
+
+        for gift in GIFTS[1..=day].iter().rev() {
+            writeln!(song, "{gift}").unwrap();
+        }
+
+        // "And a partridge" only once there's something before it.
+        let partridge = if day == 0 { "A" } else { "And a" };
+        writeln!(song, "{partridge}{}", &GIFTS[0][1..]).unwrap();
+    }
+
+    song
+}
+
+pub struct NthFibonacci;
+
+impl Lesson for NthFibonacci {
+    fn id(&self) -> &'static str {
+        "nth-fibonacci"
+    }
+
+    fn title(&self) -> &'static str {
+        "Generating Fibonnacci Numbers"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Walk the sequence with a loop up to the nth number"
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["control-flow"]
+    }
+
+    fn usage(&self) -> &'static str {
+        "nth-fibonacci <n>"
+    }
+
+    fn run(&self, ctx: &mut Context) -> Result<(), LessonError> {
+        let [n] = ctx.args() else {
+            return Err(LessonError::usage(self.usage()));
+        };
+        let n = n
+            .parse()
+            .map_err(|_| LessonError::InvalidArgs(format!("`{n}` is not a non-negative number")))?;
+        print!("{}", nth_fibonacci(n));
+        Ok(())
+    }
+}
+
+// How many steps of the sequence are spelled out before skipping to F(n).
+const SHOWN_STEPS: u64 = 12;
+
+// - Every number of the sequence is the sum of the two before it, which is
+//   shown for the first few steps.
+// - The answer itself comes from `fibonnacci`, and from fast doubling when
+//   it doesn't fit in a u64, so F(300000) doesn't walk every step.
+pub fn nth_fibonacci(n: u64) -> String {
+    let mut report = String::new();
+    let mut before = Vec::new();
+
+    for (i, number) in BigFibonacci::new()
+        .take(n.min(SHOWN_STEPS) as usize + 1)
+        .enumerate()
+    {
+        match before.as_slice() {
+            [.., a, b] => writeln!(report, "F({i}) = {a} + {b} = {number}").unwrap(),
+            _ => writeln!(report, "F({i}) = {number}").unwrap(),
+        }
+        before.push(number);
+    }
+
+    if n > SHOWN_STEPS {
+        writeln!(report, "...").unwrap();
+    }
+
+    match fibonnacci(n) {
+        Some(number) => {
+            writeln!(report, "The Fibonnacci number at index {n} is {number}.").unwrap()
+        }
+        None => {
+            let number = fibonnacci_big_with(n, FibAlgorithm::Doubling);
+            writeln!(report, "The Fibonnacci number at index {n} is {number}.").unwrap();
+            writeln!(
+                report,
+                "It has {} digits, too many for a u64, which runs out after F(93).",
+                number.to_string().len()
+            )
+            .unwrap();
+        }
+    }
+
+    report
+}
+
+pub struct Temperatures;
+
+impl Lesson for Temperatures {
+    fn id(&self) -> &'static str {
+        "temperatures"
+    }
+
+    fn title(&self) -> &'static str {
+        "Converting Temperatures"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Convert between Fahrenheit and Celsius"
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["control-flow"]
+    }
+
+    fn usage(&self) -> &'static str {
+        "temperatures [<value> F|C]"
+    }
+
+    fn run(&self, ctx: &mut Context) -> Result<(), LessonError> {
+        match ctx.args() {
+            [] => print!("{}", conversion_tables()),
+            [value, scale] => {
+                let temperature = parse_temperature(value, scale)
+                    .map_err(|err| LessonError::InvalidArgs(err.to_string()))?;
+                if !matches!(temperature.scale(), Scale::Fahrenheit | Scale::Celsius) {
+                    return Err(LessonError::usage(self.usage()));
+                }
+                print!("{}", convert(temperature));
+            }
+            _ => return Err(LessonError::usage(self.usage())),
+        }
+        Ok(())
+    }
+}
+
+fn parse_temperature(value: &str, scale: &str) -> Result<Temperature, TemperatureError> {
+    Temperature::new(value.parse()?, scale.parse()?)
+}
+
+// The other scale of the exercise and the formula that gets there.
+fn counterpart(scale: Scale) -> (Scale, &'static str) {
+    match scale {
+        Scale::Fahrenheit => (Scale::Celsius, "°C = (°F − 32) × 5/9"),
+        _ => (Scale::Fahrenheit, "°F = °C × 9/5 + 32"),
+    }
+}
+
+// A Fahrenheit temperature in Celsius or the other way around.
+pub fn convert(temperature: Temperature) -> String {
+    let (scale, formula) = counterpart(temperature.scale());
+    format!("{formula}\n{temperature} = {}\n", temperature.to(scale))
+}
+
+// - A table each way: every 20 °F from -40 °F to 220 °F, and every 10 °C
+//   from -40 °C to 100 °C.
+// - Both start at -40, the one temperature that reads the same on the two
+//   scales.
+pub fn conversion_tables() -> String {
+    let mut tables = String::new();
+    let directions = [(Scale::Fahrenheit, 220, 20), (Scale::Celsius, 100, 10)];
+
+    for (i, (from, to, step)) in directions.into_iter().enumerate() {
+        let (other, formula) = counterpart(from);
+        if i > 0 {
+            tables.push('\n');
+        }
+        writeln!(tables, "{} to {}, {formula}", from.name(), other.name()).unwrap();
+        writeln!(tables, "{:>8}{:>10}", from.symbol(), other.symbol()).unwrap();
+
+        let rows = table(
+            Rational::integer(-40),
+            Rational::integer(to),
+            Rational::integer(step),
+            from,
+        )
+        .expect("the exercise tables start above absolute zero");
+        for row in rows {
+            writeln!(
+                tables,
+                "{:>8}{:>10}",
+                row.value().to_decimal(2),
+                row.to(other).value().to_decimal(2)
+            )
+            .unwrap();
+        }
+    }
+
+    tables
+}
@@ -1,31 +1,752 @@
-fn main() {
-    // println!("Please enter which Fibonnacci number you want to find");
-    // let mut input = String::new();
-    //
-    // std::io::stdin()
-    //     .read_line(&mut input)
-    //     .expect("Failed to read line");
-    //
-    // let input: u64 = input.trim().parse().expect("Please enter a number");
-    //
-    // println!("The Fibonnacci number you are looking for is: {0}", fibonnacci(input));
-    println!("The Fibonnacci number you are looking for is: {}", fibonnacci(90));
-}
-
-fn fibonnacci(n: u64) -> u64 {
-    if n == 0 || n == 1 {
-        1
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::batch::{self, OutputFormat};
+use crate::bignum::BigUint;
+use crate::cli::ToolResult;
+use crate::lessons::{Context, Lesson, LessonError};
+use crate::scalar::{parse_scalar, ScalarError};
+
+pub struct Fib;
+
+impl Lesson for Fib {
+    fn id(&self) -> &'static str {
+        "fib"
+    }
+
+    fn title(&self) -> &'static str {
+        "Fibonnacci Numbers"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Find the nth Fibonnacci number"
+    }
+
+    fn usage(&self) -> &'static str {
+        "fib [--classic] [--algo iterative|doubling|matrix] [n]\n\
+         fib [--classic] --upto <limit> | --is <x> | --sum <from> <to>\n\
+         fib [--classic] --mod <m> <n>\n\
+         fib [--classic] [--algo ..] --batch [file] [--format text|csv|json]\n\
+         fib --bench [n]"
+    }
+
+    fn run(&self, ctx: &mut Context) -> Result<(), LessonError> {
+        let mut algorithm = None;
+        let mut indexing = FibIndexing::default();
+        let mut mode = Mode::Nth;
+        let mut format = OutputFormat::default();
+        let mut positional = None;
+
+        let mut args = ctx.args().iter().map(String::as_str);
+        while let Some(arg) = args.next() {
+            match arg {
+                "--bench" => mode = Mode::Bench,
+                "--classic" => indexing = FibIndexing::Classic,
+                "--batch" => mode = Mode::Batch,
+                "--algo" => {
+                    let name = value_of(&mut args, arg)?;
+                    algorithm = Some(name.parse().map_err(LessonError::InvalidArgs)?);
+                }
+                "--format" => {
+                    let name = value_of(&mut args, arg)?;
+                    format = name.parse().map_err(LessonError::InvalidArgs)?;
+                }
+                "--upto" => mode = Mode::UpTo(parse_n(value_of(&mut args, arg)?)?),
+                "--is" => mode = Mode::Is(parse_n(value_of(&mut args, arg)?)?),
+                "--mod" => {
+                    let m = parse_n(value_of(&mut args, arg)?)?;
+                    let n = value_of(&mut args, arg)?;
+                    let n = n.parse().map_err(|_| {
+                        LessonError::InvalidArgs(format!("`{n}` is not a non-negative number"))
+                    })?;
+                    mode = Mode::Mod(m, n);
+                }
+                "--sum" => {
+                    let from = parse_n(value_of(&mut args, arg)?)?;
+                    let to = parse_n(value_of(&mut args, arg)?)?;
+                    mode = Mode::Sum(from, to);
+                }
+                value if positional.is_none() => positional = Some(value),
+                _ => return Err(LessonError::usage(self.usage())),
+            }
+        }
+
+        let no_such_index = || {
+            LessonError::InvalidArgs(String::from(
+                "the classic sequence starts at 1, there is no 0th number",
+            ))
+        };
+        let to_standard = |n: u64| indexing.to_standard(n).ok_or_else(no_such_index);
+
+        match mode {
+            Mode::Bench => {
+                let n = positional.map(parse_n).transpose()?;
+                print_bench(n.unwrap_or(DEFAULT_BENCH_N), DEFAULT_BENCH_ROUNDS);
+            }
+            // In batch mode the positional argument is the file to read from.
+            Mode::Batch => {
+                batch::run(positional, format, indexing, algorithm.unwrap_or_default())?;
+            }
+            Mode::UpTo(limit) => print_up_to(limit, indexing),
+            Mode::Is(x) => print_is(x, indexing),
+            Mode::Mod(m, n) => {
+                if n < u128::from(indexing.first_index()) {
+                    return Err(no_such_index());
+                }
+                let remainder = fib_mod(n, m).ok_or_else(|| {
+                    LessonError::InvalidArgs(String::from("the modulus must be at least 1"))
+                })?;
+                println!("F({n}) mod {m} = {remainder}");
+            }
+            Mode::Sum(from, to) => {
+                let sum = sum_range(to_standard(from)?..=to_standard(to)?).ok_or_else(|| {
+                    LessonError::InvalidArgs(format!("the sum up to F({to}) is out of range"))
+                })?;
+                println!("The sum of the Fibonnacci numbers from {from} to {to} is: {sum}");
+            }
+            // Without an n, ask for one instead.
+            Mode::Nth => match positional {
+                Some(n) => run(to_standard(parse_n(n)?)?, algorithm.unwrap_or_default()),
+                None => prompt(
+                    io::stdin().lock(),
+                    io::stdout().lock(),
+                    indexing,
+                    algorithm.unwrap_or_default(),
+                )?,
+            },
+        }
+
+        Ok(())
+    }
+}
+
+enum Mode {
+    Nth,
+    Bench,
+    Batch,
+    UpTo(u64),
+    Is(u64),
+    Sum(u64, u64),
+    Mod(u64, u128),
+}
+
+fn value_of<'a>(
+    args: &mut impl Iterator<Item = &'a str>,
+    flag: &str,
+) -> Result<&'a str, LessonError> {
+    args.next()
+        .ok_or_else(|| LessonError::InvalidArgs(format!("`{flag}` needs a value")))
+}
+
+fn parse_n(value: &str) -> Result<u64, LessonError> {
+    value
+        .parse()
+        .map_err(|_| LessonError::InvalidArgs(format!("`{value}` is not a non-negative number")))
+}
+
+fn print_up_to(limit: u64, indexing: FibIndexing) {
+    let numbers: Vec<String> = Fibonacci::new()
+        .take_until(limit)
+        .skip(indexing.first_index() as usize)
+        .map(|number| number.to_string())
+        .collect();
+
+    println!(
+        "The Fibonnacci numbers up to {limit} are: {}",
+        numbers.join(", ")
+    );
+}
+
+fn print_is(x: u64, indexing: FibIndexing) {
+    match index_of(x).map(|n| indexing.to_standard(n)) {
+        Some(Some(n)) => println!("{x} is Fibonnacci number {n}"),
+        Some(None) => println!("{x} is not part of the classic sequence"),
+        None => println!("{x} is not a Fibonnacci number"),
+    }
+}
+
+pub fn run(n: u64, algorithm: FibAlgorithm) {
+    println!("{}", answer(n, algorithm));
+}
+
+// Anything past MAX_U64_N doesn't fit in a u64, so the big integer version
+// takes over instead of overflowing.
+fn answer(n: u64, algorithm: FibAlgorithm) -> String {
+    match fibonnacci_with(n, algorithm) {
+        Some(number) => format!("The Fibonnacci number you are looking for is: {number}"),
+        None => format!(
+            "The Fibonnacci number you are looking for is: {}",
+            fibonnacci_big_with(n, algorithm)
+        ),
+    }
+}
+
+// The prompt refuses anything larger so a typo can't keep it busy for
+// minutes; cpc fib <n> has no such limit.
+pub const MAX_PROMPT_N: u64 = 1_000_000;
+
+// - Keeps asking for n until it gets quit or the input runs out.
+// - Anything that isn't a usable index gets an explanation and the question
+//   again, instead of ending the program like `.expect()` would.
+pub fn prompt(
+    mut input: impl BufRead,
+    mut output: impl Write,
+    indexing: FibIndexing,
+    algorithm: FibAlgorithm,
+) -> io::Result<()> {
+    loop {
+        writeln!(
+            output,
+            "Please enter which Fibonnacci number you want to find (or quit):"
+        )?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+
+        let n = match parse_prompt(line.trim(), indexing) {
+            Ok(Some(n)) => n,
+            Ok(None) => return Ok(()),
+            Err(message) => {
+                writeln!(output, "{message}")?;
+                continue;
+            }
+        };
+
+        if n > MAX_U64_N {
+            writeln!(
+                output,
+                "F({n}) is too large for a u64, switching to big integers."
+            )?;
+        }
+        writeln!(output, "{}", answer(n, algorithm))?;
+    }
+}
+
+// Ok(None) means the user asked to quit.
+fn parse_prompt(text: &str, indexing: FibIndexing) -> Result<Option<u64>, String> {
+    if matches!(text.to_ascii_lowercase().as_str(), "quit" | "q" | "exit") {
+        return Ok(None);
+    }
+
+    let out_of_range =
+        || format!("{text} is out of range, please enter a number up to {MAX_PROMPT_N}.");
+
+    let n = parse_scalar::<u64>(text).map_err(|err| match err {
+        ScalarError::Empty { .. } => String::from("Please type a number."),
+        ScalarError::Overflow { .. } => out_of_range(),
+        ScalarError::Underflow { .. } => String::from(
+            "There are no Fibonnacci numbers at negative positions, please enter 0 or more.",
+        ),
+        err => format!("{err}, please enter digits only."),
+    })?;
+
+    if n > MAX_PROMPT_N {
+        return Err(out_of_range());
+    }
+
+    indexing
+        .to_standard(n)
+        .map(Some)
+        .ok_or_else(|| String::from("The classic sequence starts at 1, please enter 1 or more."))
+}
+
+// The largest n whose Fibonnacci number still fits in a u64.
+pub const MAX_U64_N: u64 = 93;
+
+// - Every function in this module uses the standard numbering, where
+//   F(0) = 0, F(1) = 1 and F(n + 2) = F(n + 1) + F(n):
+//     n:    0  1  2  3  4  5  6  7
+//     F(n): 0  1  1  2  3  5  8  13
+// - The classic numbering found in older textbooks counts from one and has
+//   no 0th number: the 1st and 2nd numbers are both 1. Since both agree from
+//   n = 1 onwards, a classic index is converted to a standard one by only
+//   rejecting 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FibIndexing {
+    #[default]
+    Standard,
+    Classic,
+}
+
+impl FibIndexing {
+    pub fn first_index(self) -> u64 {
+        match self {
+            FibIndexing::Standard => 0,
+            FibIndexing::Classic => 1,
+        }
+    }
+
+    // - Returns None for indices the numbering doesn't have.
+    // - Both numberings give a number the same index wherever both have
+    //   one, so this also turns a standard index back into one of `self`.
+    pub fn to_standard(self, n: u64) -> Option<u64> {
+        (n >= self.first_index()).then_some(n)
+    }
+}
+
+// - Iterative walks the sequence one number at a time, which takes n steps.
+// - Doubling and Matrix both halve n on every step, so they only take about
+//   log2(n) steps, although each step does a few multiplications.
+// - Doubling is the default: F(300000) takes it well under a second, and
+//   Iterative closer to a minute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FibAlgorithm {
+    Iterative,
+    #[default]
+    Doubling,
+    Matrix,
+}
+
+impl FibAlgorithm {
+    pub const ALL: [FibAlgorithm; 3] = [
+        FibAlgorithm::Iterative,
+        FibAlgorithm::Doubling,
+        FibAlgorithm::Matrix,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FibAlgorithm::Iterative => "iterative",
+            FibAlgorithm::Doubling => "doubling",
+            FibAlgorithm::Matrix => "matrix",
+        }
+    }
+}
+
+impl fmt::Display for FibAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for FibAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FibAlgorithm::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.name() == s)
+            .ok_or_else(|| {
+                format!("unknown algorithm `{s}`, expected iterative, doubling or matrix")
+            })
+    }
+}
+
+// - Returns None once the result no longer fits in a u64 (n > MAX_U64_N)
+//   rather than panicking in debug builds or wrapping around in release.
+pub fn fibonnacci(n: u64) -> Option<u64> {
+    if n == 0 {
+        Some(0)
     } else {
-        let mut counter = 2;
-        let mut prior = 1;
-        let mut current = 1;
+        let mut counter = 1;
+        let mut prior: u64 = 0;
+        let mut current: u64 = 1;
 
-        // 1, 1, 2, 3, 5, 8, 13
+        // 0, 1, 1, 2, 3, 5, 8, 13
         while counter < n {
-            current += prior;
+            current = current.checked_add(prior)?;
             prior = current - prior;
             counter += 1;
         }
+        Some(current)
+    }
+}
+
+// - Same sequence as fibonnacci, but the numbers are BigUint so they never
+//   overflow; the only limit is memory and patience.
+pub fn fibonnacci_big(n: u64) -> BigUint {
+    if n == 0 {
+        BigUint::zero()
+    } else {
+        let mut counter = 1;
+        let mut prior = BigUint::zero();
+        let mut current = BigUint::one();
+
+        // Adding in place and swapping avoids allocating a new number on
+        // every step: prior becomes the next number, then trades places.
+        while counter < n {
+            prior += &current;
+            std::mem::swap(&mut prior, &mut current);
+            counter += 1;
+        }
         current
     }
 }
+
+pub fn fibonnacci_with(n: u64, algorithm: FibAlgorithm) -> Option<u64> {
+    match algorithm {
+        FibAlgorithm::Iterative => fibonnacci(n),
+        FibAlgorithm::Doubling | FibAlgorithm::Matrix if n > MAX_U64_N => None,
+        // Below MAX_U64_N + 1 every intermediate value fits in a u128, the
+        // pair (F(n), F(n + 1)) included, so no overflow checks are needed.
+        FibAlgorithm::Doubling => Some(doubling_u128(n).0 as u64),
+        FibAlgorithm::Matrix => Some(matrix_u128(n)[0][1] as u64),
+    }
+}
+
+pub fn fibonnacci_big_with(n: u64, algorithm: FibAlgorithm) -> BigUint {
+    match algorithm {
+        FibAlgorithm::Iterative => fibonnacci_big(n),
+        FibAlgorithm::Doubling => doubling_big(n).0,
+        FibAlgorithm::Matrix => {
+            let [[_, f_n], _] = matrix_big(n);
+            f_n
+        }
+    }
+}
+
+// - Fast doubling works from the top bit of n down, keeping the pair
+//   (F(k), F(k + 1)) and using
+//     F(2k)     = F(k) * (2 * F(k + 1) - F(k))
+//     F(2k + 1) = F(k)^2 + F(k + 1)^2
+//   to double k at every bit, adding one more when the bit is set.
+fn doubling_u128(n: u64) -> (u128, u128) {
+    let (mut a, mut b): (u128, u128) = (0, 1);
+
+    for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+        let c = a * (2 * b - a);
+        let d = a * a + b * b;
+
+        (a, b) = if n >> bit & 1 == 1 {
+            (d, c + d)
+        } else {
+            (c, d)
+        };
+    }
+
+    (a, b)
+}
+
+fn doubling_big(n: u64) -> (BigUint, BigUint) {
+    let (mut a, mut b) = (BigUint::zero(), BigUint::one());
+
+    for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+        let c = &a * &(&(&b + &b) - &a);
+        let d = &(&a * &a) + &(&b * &b);
+
+        (a, b) = if n >> bit & 1 == 1 {
+            let sum = &c + &d;
+            (d, sum)
+        } else {
+            (c, d)
+        };
+    }
+
+    (a, b)
+}
+
+// - The matrix [[1, 1], [1, 0]] raised to the nth power is
+//   [[F(n + 1), F(n)], [F(n), F(n - 1)]], and the power is found by repeated
+//   squaring.
+type Matrix<T> = [[T; 2]; 2];
+
+fn matrix_u128(n: u64) -> Matrix<u128> {
+    fn mul(x: &Matrix<u128>, y: &Matrix<u128>) -> Matrix<u128> {
+        [
+            [
+                x[0][0] * y[0][0] + x[0][1] * y[1][0],
+                x[0][0] * y[0][1] + x[0][1] * y[1][1],
+            ],
+            [
+                x[1][0] * y[0][0] + x[1][1] * y[1][0],
+                x[1][0] * y[0][1] + x[1][1] * y[1][1],
+            ],
+        ]
+    }
+
+    let mut result = [[1, 0], [0, 1]];
+    let mut base = [[1, 1], [1, 0]];
+    let mut n = n;
+
+    while n > 0 {
+        if n & 1 == 1 {
+            result = mul(&result, &base);
+        }
+        n >>= 1;
+        // Squaring once more than needed would overflow for the largest n.
+        if n > 0 {
+            base = mul(&base, &base);
+        }
+    }
+
+    result
+}
+
+fn matrix_big(n: u64) -> Matrix<BigUint> {
+    fn mul(x: &Matrix<BigUint>, y: &Matrix<BigUint>) -> Matrix<BigUint> {
+        let cell = |row: usize, col: usize| &(&x[row][0] * &y[0][col]) + &(&x[row][1] * &y[1][col]);
+
+        [[cell(0, 0), cell(0, 1)], [cell(1, 0), cell(1, 1)]]
+    }
+
+    let mut result = [
+        [BigUint::one(), BigUint::zero()],
+        [BigUint::zero(), BigUint::one()],
+    ];
+    let mut base = [
+        [BigUint::one(), BigUint::one()],
+        [BigUint::one(), BigUint::zero()],
+    ];
+    let mut n = n;
+
+    while n > 0 {
+        if n & 1 == 1 {
+            result = mul(&result, &base);
+        }
+        n >>= 1;
+        if n > 0 {
+            base = mul(&base, &base);
+        }
+    }
+
+    result
+}
+
+const DEFAULT_BENCH_N: u64 = 10_000;
+const DEFAULT_BENCH_ROUNDS: u32 = 5;
+
+// Fastest of `rounds` runs of the big integer version of each algorithm.
+pub fn bench(n: u64, rounds: u32) -> Vec<(FibAlgorithm, Duration)> {
+    FibAlgorithm::ALL
+        .into_iter()
+        .map(|algorithm| {
+            let fastest = (0..rounds.max(1))
+                .map(|_| {
+                    let start = Instant::now();
+                    std::hint::black_box(fibonnacci_big_with(std::hint::black_box(n), algorithm));
+                    start.elapsed()
+                })
+                .min()
+                .unwrap_or_default();
+
+            (algorithm, fastest)
+        })
+        .collect()
+}
+
+fn print_bench(n: u64, rounds: u32) {
+    println!("Computing F({n}), best of {rounds} rounds:");
+
+    for (algorithm, elapsed) in bench(n, rounds) {
+        println!("  {:<10} {:>12.3?}", algorithm.name(), elapsed);
+    }
+}
+
+// - Fibonnacci yields the sequence as u64 values, starting from F(0).
+// - It stops after F(MAX_U64_N) instead of overflowing, so it can be used in
+//   a for loop without a take.
+#[derive(Debug, Clone)]
+pub struct Fibonacci {
+    current: Option<u64>,
+    next: Option<u64>,
+}
+
+impl Fibonacci {
+    pub fn new() -> Self {
+        Self {
+            current: Some(0),
+            next: Some(1),
+        }
+    }
+
+    // Every number that is less than or equal to limit.
+    pub fn take_until(self, limit: u64) -> impl Iterator<Item = u64> {
+        self.take_while(move |&number| number <= limit)
+    }
+}
+
+impl Default for Fibonacci {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for Fibonacci {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let current = self.current?;
+
+        self.current = self.next;
+        self.next = self.next.and_then(|next| next.checked_add(current));
+
+        Some(current)
+    }
+}
+
+// Same as Fibonacci but with BigUint values, so it never runs out.
+#[derive(Debug, Clone)]
+pub struct BigFibonacci {
+    current: BigUint,
+    next: BigUint,
+}
+
+impl BigFibonacci {
+    pub fn new() -> Self {
+        Self {
+            current: BigUint::zero(),
+            next: BigUint::one(),
+        }
+    }
+}
+
+impl Default for BigFibonacci {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for BigFibonacci {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<BigUint> {
+        let following = &self.current + &self.next;
+        let next = std::mem::replace(&mut self.next, following);
+
+        Some(std::mem::replace(&mut self.current, next))
+    }
+}
+
+// - Returns the standard index of x in the sequence.
+// - 1 appears twice, as F(1) and F(2); the first one is returned.
+pub fn index_of(x: u64) -> Option<u64> {
+    Fibonacci::new()
+        .take_until(x)
+        .position(|number| number == x)
+        .map(|n| n as u64)
+}
+
+pub fn is_fibonacci(x: u64) -> bool {
+    index_of(x).is_some()
+}
+
+// - Adds up F(start) through F(end), inclusive.
+// - Uses F(0) + F(1) + ... + F(n) = F(n + 2) - 1, so the sum of the range is
+//   F(end + 2) - F(start + 1) and no numbers have to be added one by one.
+// - Returns None when end + 2 doesn't fit in a u64.
+pub fn sum_range(range: RangeInclusive<u64>) -> Option<BigUint> {
+    let (start, end) = range.into_inner();
+    if start > end {
+        return Some(BigUint::zero());
+    }
+
+    let upper = fibonnacci_big_with(end.checked_add(2)?, FibAlgorithm::Doubling);
+    let lower = fibonnacci_big_with(start + 1, FibAlgorithm::Doubling);
+
+    Some(&upper - &lower)
+}
+
+// - F(n) mod m, using the same fast doubling as fibonnacci_with but reducing
+//   every step mod m, so n can be as large as a u128 can hold.
+// - Every intermediate value is below m, so the products fit in a u128.
+// - Returns None for m = 0.
+pub fn fib_mod(n: u128, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+
+    let m = u128::from(m);
+    let (mut a, mut b) = (0, 1 % m);
+
+    for bit in (0..u128::BITS - n.leading_zeros()).rev() {
+        let c = a * ((2 * b + m - a) % m) % m;
+        let d = (a * a % m + b * b % m) % m;
+
+        (a, b) = if n >> bit & 1 == 1 {
+            (d, (c + d) % m)
+        } else {
+            (c, d)
+        };
+    }
+
+    Some(a as u64)
+}
+
+// - The Pisano period is the length of the cycle the sequence repeats with
+//   once it is taken mod m, e.g. mod 3: 0 1 1 2 0 2 2 1 | 0 1 1 2 ...
+//   has a period of 8.
+// - Walking the sequence until it returns to 0, 1 can take up to 6m steps,
+//   so instead a known multiple of the period is built from the prime factors
+//   of m and then divided down for as long as it stays a period:
+//     - the period mod p^k divides p^(k - 1) times the period mod p
+//     - the period mod a prime p divides p - 1 when p ends in 1 or 9 (mod 10)
+//       and 2(p + 1) otherwise, except for 2 (3) and 5 (20)
+//     - the period mod m is the lcm of the periods of its prime powers
+// - Returns None for m = 0, which has no period.
+pub fn pisano_period(m: u64) -> Option<u128> {
+    if m == 0 {
+        return None;
+    }
+
+    let mut multiple: BTreeMap<u128, u32> = BTreeMap::new();
+
+    for (p, k) in factorize(u128::from(m)) {
+        let mut factors = match p {
+            2 => factorize(3),
+            5 => factorize(20),
+            p if p % 5 == 1 || p % 5 == 4 => factorize(p - 1),
+            p => factorize(2 * (p + 1)),
+        };
+        *factors.entry(p).or_default() += k - 1;
+
+        // lcm: keep the highest power of every prime.
+        for (q, e) in factors {
+            let exponent = multiple.entry(q).or_default();
+            *exponent = (*exponent).max(e);
+        }
+    }
+
+    let mut period: u128 = multiple.iter().map(|(q, e)| q.pow(*e)).product();
+    let is_period = |t: u128| fib_mod(t, m) == Some(0) && fib_mod(t + 1, m) == Some(1 % m);
+
+    for (q, e) in multiple {
+        for _ in 0..e {
+            if !is_period(period / q) {
+                break;
+            }
+            period /= q;
+        }
+    }
+
+    Some(period)
+}
+
+// Prime factors of n with their exponents, found by trial division.
+fn factorize(mut n: u128) -> BTreeMap<u128, u32> {
+    let mut factors = BTreeMap::new();
+    let mut divisor = 2;
+
+    while divisor * divisor <= n {
+        while n.is_multiple_of(divisor) {
+            *factors.entry(divisor).or_default() += 1;
+            n /= divisor;
+        }
+        divisor += if divisor == 2 { 1 } else { 2 };
+    }
+
+    if n > 1 {
+        *factors.entry(n).or_default() += 1;
+    }
+
+    factors
+}
+
+pub fn pisano_command(args: &[String]) -> ToolResult {
+    let [m] = args else {
+        return Err("usage: cpc pisano <m>".into());
+    };
+
+    let m: u64 = m
+        .parse()
+        .map_err(|_| format!("`{m}` is not a non-negative number"))?;
+    let period = pisano_period(m).ok_or("the modulus must be at least 1")?;
+
+    println!("The Pisano period of {m} is: {period}");
+    Ok(())
+}
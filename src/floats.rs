@@ -0,0 +1,533 @@
+use std::error::Error;
+use std::fmt;
+use std::num::FpCategory;
+use std::str::FromStr;
+
+use crate::cli::ToolResult;
+use crate::decimal::{Decimal, Scientific};
+
+// The two IEEE 754 binary formats Rust has: f32 is single precision and f64,
+// the default, is double precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FloatType {
+    F32,
+    F64,
+}
+
+impl FloatType {
+    pub const ALL: [FloatType; 2] = [FloatType::F32, FloatType::F64];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FloatType::F32 => "f32",
+            FloatType::F64 => "f64",
+        }
+    }
+
+    pub fn bits(self) -> u32 {
+        match self {
+            FloatType::F32 => 32,
+            FloatType::F64 => 64,
+        }
+    }
+
+    pub fn exponent_bits(self) -> u32 {
+        match self {
+            FloatType::F32 => 8,
+            FloatType::F64 => 11,
+        }
+    }
+
+    // Not counting the leading 1 that normal numbers leave out.
+    pub fn mantissa_bits(self) -> u32 {
+        self.bits() - self.exponent_bits() - 1
+    }
+
+    // What is subtracted from the stored exponent: 127 for f32, 1023 for f64.
+    pub fn bias(self) -> i32 {
+        (1 << (self.exponent_bits() - 1)) - 1
+    }
+}
+
+impl fmt::Display for FloatType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for FloatType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "f32" => Ok(FloatType::F32),
+            "f64" => Ok(FloatType::F64),
+            other => Err(format!("`{other}` is not a float type")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FloatError {
+    Empty,
+    Invalid {
+        text: String,
+        ty: FloatType,
+    },
+    MismatchedTypes {
+        expected: FloatType,
+        found: FloatType,
+    },
+}
+
+impl fmt::Display for FloatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FloatError::Empty => f.write_str("expected a float literal, found nothing"),
+            FloatError::Invalid { text, ty } => write!(f, "`{text}` is not a valid `{ty}`"),
+            FloatError::MismatchedTypes { expected, found } => {
+                write!(
+                    f,
+                    "mismatched types: expected `{expected}`, found `{found}`"
+                )
+            }
+        }
+    }
+}
+
+impl Error for FloatError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FloatValue {
+    F32(f32),
+    F64(f64),
+}
+
+// The three fields a float is stored as, still in their raw form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FloatParts {
+    pub negative: bool,
+    pub exponent: u32,
+    pub mantissa: u64,
+}
+
+impl FloatValue {
+    // - Accepts what Rust accepts as a float literal, 2.0, 1e-3, 1_000.5 or
+    //   0.1f32, plus inf and NaN.
+    // - The text is parsed straight into `ty`: going through an f64 first
+    //   could round twice and give a different f32.
+    pub fn parse(text: &str, ty: FloatType) -> Result<FloatValue, FloatError> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(FloatError::Empty);
+        }
+
+        let (digits, suffix) = FloatType::ALL
+            .into_iter()
+            .find_map(|found| {
+                let digits = text.strip_suffix(found.name())?;
+                let digits = digits.strip_suffix('_').unwrap_or(digits);
+                (!digits.is_empty()).then_some((digits, Some(found)))
+            })
+            .unwrap_or((text, None));
+
+        if let Some(found) = suffix.filter(|&found| found != ty) {
+            return Err(FloatError::MismatchedTypes {
+                expected: ty,
+                found,
+            });
+        }
+
+        let digits = digits.replace('_', "");
+        let invalid = || FloatError::Invalid {
+            text: text.to_string(),
+            ty,
+        };
+
+        match ty {
+            FloatType::F32 => digits.parse().map(FloatValue::F32).map_err(|_| invalid()),
+            FloatType::F64 => digits.parse().map(FloatValue::F64).map_err(|_| invalid()),
+        }
+    }
+
+    pub fn ty(self) -> FloatType {
+        match self {
+            FloatValue::F32(_) => FloatType::F32,
+            FloatValue::F64(_) => FloatType::F64,
+        }
+    }
+
+    pub fn to_bits(self) -> u64 {
+        match self {
+            FloatValue::F32(v) => v.to_bits().into(),
+            FloatValue::F64(v) => v.to_bits(),
+        }
+    }
+
+    pub fn classify(self) -> FpCategory {
+        match self {
+            FloatValue::F32(v) => v.classify(),
+            FloatValue::F64(v) => v.classify(),
+        }
+    }
+
+    pub fn parts(self) -> FloatParts {
+        let ty = self.ty();
+        let bits = self.to_bits();
+        let mantissa_bits = ty.mantissa_bits();
+
+        FloatParts {
+            negative: bits >> (ty.bits() - 1) == 1,
+            exponent: ((bits >> mantissa_bits) & ((1 << ty.exponent_bits()) - 1)) as u32,
+            mantissa: bits & ((1 << mantissa_bits) - 1),
+        }
+    }
+}
+
+// Written like {:?} writes floats, so 2.0 keeps its .0 and -0.0 its sign.
+impl fmt::Display for FloatValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FloatValue::F32(v) => fmt::Debug::fmt(v, f),
+            FloatValue::F64(v) => fmt::Debug::fmt(v, f),
+        }
+    }
+}
+
+impl FloatValue {
+    // Every f32 is also an f64, so this never rounds.
+    pub fn to_f64(self) -> f64 {
+        match self {
+            FloatValue::F32(v) => v.into(),
+            FloatValue::F64(v) => v,
+        }
+    }
+
+    pub fn is_finite(self) -> bool {
+        self.to_f64().is_finite()
+    }
+
+    // The closest value of the same type above this one.
+    pub fn next_up(self) -> FloatValue {
+        match self {
+            FloatValue::F32(v) => FloatValue::F32(v.next_up()),
+            FloatValue::F64(v) => FloatValue::F64(v.next_up()),
+        }
+    }
+
+    // The closest value of the same type below this one.
+    pub fn next_down(self) -> FloatValue {
+        match self {
+            FloatValue::F32(v) => FloatValue::F32(v.next_down()),
+            FloatValue::F64(v) => FloatValue::F64(v.next_down()),
+        }
+    }
+
+    // - The unit in the last place: the gap between this value and the next
+    //   one further from zero, i.e. how much the last mantissa bit is worth.
+    // - The largest finite value has nothing above it, so the gap below is
+    //   used there.
+    pub fn ulp(self) -> f64 {
+        let magnitude = match self {
+            FloatValue::F32(v) => FloatValue::F32(v.abs()),
+            FloatValue::F64(v) => FloatValue::F64(v.abs()),
+        };
+
+        let above = magnitude.next_up();
+        if above.is_finite() {
+            above.to_f64() - magnitude.to_f64()
+        } else {
+            magnitude.to_f64() - magnitude.next_down().to_f64()
+        }
+    }
+
+    // The exact value as a decimal, None for infinities and NaN.
+    pub fn exact(self) -> Option<Decimal> {
+        Decimal::from_f64(self.to_f64())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl FloatOp {
+    pub fn symbol(self) -> char {
+        match self {
+            FloatOp::Add => '+',
+            FloatOp::Sub => '-',
+            FloatOp::Mul => '*',
+            FloatOp::Div => '/',
+        }
+    }
+
+    fn from_symbol(symbol: char) -> Option<FloatOp> {
+        match symbol {
+            '+' => Some(FloatOp::Add),
+            '-' => Some(FloatOp::Sub),
+            '*' => Some(FloatOp::Mul),
+            '/' => Some(FloatOp::Div),
+            _ => None,
+        }
+    }
+
+    // Both operands must have the same type, the result has it too.
+    pub fn apply(self, a: FloatValue, b: FloatValue) -> FloatValue {
+        match (a, b) {
+            (FloatValue::F32(a), FloatValue::F32(b)) => FloatValue::F32(match self {
+                FloatOp::Add => a + b,
+                FloatOp::Sub => a - b,
+                FloatOp::Mul => a * b,
+                FloatOp::Div => a / b,
+            }),
+            (FloatValue::F64(a), FloatValue::F64(b)) => FloatValue::F64(match self {
+                FloatOp::Add => a + b,
+                FloatOp::Sub => a - b,
+                FloatOp::Mul => a * b,
+                FloatOp::Div => a / b,
+            }),
+            _ => panic!("mismatched float types: {} and {}", a.ty(), b.ty()),
+        }
+    }
+
+    // - How far `result` is from the exact result of `a op b`, where a and b
+    //   are taken at their exact decimal values. None when it's exact.
+    // - Sums, differences and products of decimals are decimals, so those
+    //   are exact. A quotient usually isn't: for r = a / b the error is
+    //   (r * b - a) / b, which is zero exactly when r * b - a is, and only
+    //   that last division is rounded.
+    pub fn error(self, a: &Decimal, b: &Decimal, result: &Decimal) -> Option<Scientific> {
+        match self {
+            FloatOp::Add => (result - &(a + b)).to_scientific(),
+            FloatOp::Sub => (result - &(a - b)).to_scientific(),
+            FloatOp::Mul => (result - &(a * b)).to_scientific(),
+            FloatOp::Div => {
+                let remainder = (&(result * b) - a).to_scientific()?;
+                Some(remainder / b.to_scientific()?)
+            }
+        }
+    }
+}
+
+// `lhs op rhs`, or just `lhs` when there's no operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Expression<'a> {
+    pub lhs: &'a str,
+    pub rhs: Option<(FloatOp, &'a str)>,
+}
+
+// - Splits "5.0 + 10.9" into its operands and operator; a lone number has
+//   no operator.
+// - The operator is the first + - * or / that follows part of a number,
+//   which skips leading signs and the sign of an exponent like 1e-3.
+pub fn split_expression(text: &str) -> Result<Expression<'_>, String> {
+    let text = text.trim();
+    let mut previous = None;
+
+    for (i, c) in text.char_indices() {
+        if let (Some(op), Some(before)) = (FloatOp::from_symbol(c), previous) {
+            if !matches!(before, 'e' | 'E') && FloatOp::from_symbol(before).is_none() {
+                let (a, b) = (text[..i].trim(), text[i + 1..].trim());
+                if b.is_empty() {
+                    return Err(format!("`{text}` is missing the right-hand side of {c}"));
+                }
+                return Ok(Expression {
+                    lhs: a,
+                    rhs: Some((op, b)),
+                });
+            }
+        }
+        if !c.is_whitespace() {
+            previous = Some(c);
+        }
+    }
+
+    Ok(Expression {
+        lhs: text,
+        rhs: None,
+    })
+}
+
+fn without_suffix(text: &str) -> &str {
+    let text = text.trim();
+    let text = text
+        .strip_suffix("f32")
+        .or_else(|| text.strip_suffix("f64"))
+        .unwrap_or(text);
+    text.strip_suffix('_').unwrap_or(text)
+}
+
+fn suffix(text: &str) -> Option<FloatType> {
+    FloatType::ALL
+        .into_iter()
+        .find(|ty| text.trim().ends_with(ty.name()) && text.trim() != ty.name())
+}
+
+// - Whether a value is exact is decided on the decimals, before anything
+//   is rounded: 5e-324 is off from the f64 it's stored as by less than the
+//   smallest f64, which would round to 0.
+// - The ulp is a power of two, so it converts exactly.
+fn describe_error(error: Option<Scientific>, ulp: f64) -> String {
+    let Some(error) = error else {
+        return String::from("exact");
+    };
+    let ulp = Decimal::from_f64(ulp)
+        .and_then(|ulp| ulp.to_scientific())
+        .expect("an ulp is finite and nonzero");
+
+    format!(
+        "off by {:+}e{}, {:.3} ulp",
+        error.mantissa,
+        error.exponent,
+        (error / ulp).to_f64()
+    )
+}
+
+// Everything about `a op b`, or just `a`, worked out in one float type.
+fn print_report(ty: FloatType, expression: Expression) -> Result<FloatValue, FloatError> {
+    let Expression { lhs: a, rhs } = expression;
+    let x = FloatValue::parse(a, ty)?;
+    let operands = match rhs {
+        Some((op, b)) => Some((op, b, FloatValue::parse(b, ty)?)),
+        None => None,
+    };
+    let result = match operands {
+        Some((op, _, y)) => op.apply(x, y),
+        None => x,
+    };
+
+    println!("{ty}");
+
+    // What each literal was rounded to when it was stored.
+    let mut literals = vec![(a, x)];
+    if let Some((_, b, y)) = operands {
+        literals.push((b, y));
+    }
+    let width = literals
+        .iter()
+        .map(|(text, _)| text.len())
+        .max()
+        .unwrap_or(0)
+        .max(10);
+
+    if operands.is_some() {
+        for (text, value) in &literals {
+            let written = without_suffix(text).parse::<Decimal>().ok();
+            match (written, value.exact()) {
+                (Some(written), Some(stored)) if written == stored => {
+                    println!("  {text:width$}  stored exactly");
+                }
+                (Some(written), Some(stored)) => println!(
+                    "  {text:width$}  stored as {stored}, {}",
+                    describe_error((&stored - &written).to_scientific(), value.ulp())
+                ),
+                _ => println!("  {text:width$}  stored as {value}"),
+            }
+        }
+    }
+
+    println!("  {:width$}  {result}", "result");
+
+    let Some(exact) = result.exact() else {
+        println!("  {:width$}  not a finite number", "exact");
+        return Ok(result);
+    };
+    let ulp = result.ulp();
+
+    println!("  {:width$}  {exact}", "exact");
+    for (label, neighbour) in [("below", result.next_down()), ("above", result.next_up())] {
+        match neighbour.exact() {
+            Some(neighbour) => println!("  {label:width$}  {neighbour}"),
+            None => println!("  {label:width$}  {neighbour}"),
+        }
+    }
+    println!("  {:width$}  {ulp:e}", "ulp");
+
+    let Some((op, b, y)) = operands else {
+        if let Ok(written) = without_suffix(a).parse::<Decimal>() {
+            let error = (&exact - &written).to_scientific();
+            println!(
+                "  {:width$}  {}",
+                format!("vs {}", without_suffix(a)),
+                describe_error(error, ulp)
+            );
+        }
+        return Ok(result);
+    };
+
+    // How much the operation itself rounded, and how far the result ended up
+    // from what the literals say on paper.
+    if let (Some(stored_a), Some(stored_b)) = (x.exact(), y.exact()) {
+        let error = op.error(&stored_a, &stored_b, &exact);
+        println!("  {:width$}  {}", "rounding", describe_error(error, ulp));
+    }
+
+    let written = (
+        without_suffix(a).parse::<Decimal>(),
+        without_suffix(b).parse::<Decimal>(),
+    );
+    if let (Ok(written_a), Ok(written_b)) = written {
+        let error = op.error(&written_a, &written_b, &exact);
+        println!("  {:width$}  {}", "vs paper", describe_error(error, ulp));
+    }
+
+    Ok(result)
+}
+
+// - cpc float 5.0 + 10.9 shows how both literals and the sum are stored, in
+//   f64 and in f32, and how far each is from the decimal on paper.
+// - A suffix like 0.1f32 limits the report to that type.
+pub fn float_command(args: &[String]) -> ToolResult {
+    if args.is_empty() {
+        return Err("usage: cpc float <number> | <a> <+|-|*|/> <b>".into());
+    }
+
+    let text = args.join(" ");
+    let expression = split_expression(&text)?;
+    let Expression { lhs: a, rhs } = expression;
+
+    let suffixes: Vec<FloatType> = [Some(a), rhs.map(|(_, b)| b)]
+        .into_iter()
+        .flatten()
+        .filter_map(suffix)
+        .collect();
+    let types = match suffixes.first() {
+        Some(&ty) => vec![ty],
+        None => vec![FloatType::F64, FloatType::F32],
+    };
+
+    // Check both operands before printing anything.
+    FloatValue::parse(a, types[0])?;
+    if let Some((_, b)) = rhs {
+        FloatValue::parse(b, types[0])?;
+    }
+
+    println!("{text}");
+    let mut results = Vec::new();
+    for ty in types {
+        println!();
+        results.push(print_report(ty, expression)?);
+    }
+
+    if let [FloatValue::F64(wide), FloatValue::F32(narrow)] = results[..] {
+        if let (Some(wide), Some(narrow_exact)) =
+            (Decimal::from_f64(wide), Decimal::from_f64(narrow.into()))
+        {
+            println!();
+            if wide == narrow_exact {
+                println!("f32 and f64 give the same value.");
+            } else {
+                println!(
+                    "The f32 result is {narrow_exact} as an f64, {:+e} away from the f64 result.",
+                    (&narrow_exact - &wide).to_f64()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,4 +1,34 @@
-fn main() {
+use crate::lessons::{Context, Lesson, LessonError};
+use crate::numeric::{five, plus_one, plus_two, saturating_add_n};
+use crate::units::Quantity;
+
+pub struct Functions;
+
+impl Lesson for Functions {
+    fn id(&self) -> &'static str {
+        "functions"
+    }
+
+    fn title(&self) -> &'static str {
+        "Functions"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Parameters, statements, expressions and return values"
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["data-types"]
+    }
+
+    fn run(&self, ctx: &mut Context) -> Result<(), LessonError> {
+        ctx.expect_no_args(self.id())?;
+        run();
+        Ok(())
+    }
+}
+
+pub fn run() {
     println!("Hello, world");
 
     another_function();
@@ -14,9 +44,11 @@ fn main() {
     *    ments.
     */
 
-    const PARAGON_LVL: u8 = 235;
+    // Paragon levels go past 255, so a u8 isn't enough. `cpc character` keeps
+    // track of a whole character, level, paragon and XP.
+    const PARAGON_LVL: u16 = 235;
     some_function(PARAGON_LVL);
-    print_labeled_measurement(5, 'm');
+    print_labeled_measurement(5.0, "m");
 
     /* - Statements do not return values. Therefore, you can't assign a let
     *    statement to another variable.
@@ -58,7 +90,7 @@ fn main() {
      *   ecifying a value, but most function return the last expression implicitly
      */
 
-    let z = five();
+    let z: i32 = five();
 
     println!("The value of z is: {z}");
 
@@ -70,6 +102,16 @@ fn main() {
 
     println!("The value of t is: {w}");
 
+    // - five, plus_one and plus_two are generic, so they work on any integer
+    //   or float type, not just i32.
+    // - plus_one(i32::MAX) still overflows like i32::MAX + 1 would; the
+    //   saturating version stops at the largest value instead.
+    let u = plus_one(u8::MAX - 1);
+    let f = plus_two(0.5);
+    let s = saturating_add_n(i32::MAX, five());
+
+    println!("plus_one(254u8) is {u}, plus_two(0.5) is {f}, i32::MAX + 5 saturates to {s}");
+
     let arr = [5; 19];
 
     for number in arr {
@@ -81,12 +123,12 @@ fn another_function() {
     println!("Another function");
 }
 
-fn some_function(x: u8) {
+fn some_function(x: u16) {
     println!("I am currently at {x} paragon level on Diablo IV.");
 }
 
 /* - The declaration of some_function has one parameter name x.
-*  - The type of x is specified as u8. When we pass PARAGON_LVL into the function
+*  - The type of x is specified as u16. When we pass PARAGON_LVL into the function
 *    the println! macro puts PARAGON_LVL where the pair of curly brackets contai-
 *    ning x was in the format string.
 *  - In function signatures, you must declare the type of each parameter.
@@ -107,15 +149,21 @@ fn some_function(x: u8) {
 *    ment.
 * **/
 
-fn print_labeled_measurement(value: i32, unit_label: char) {
-    println!("The measurement is: {value}{unit_label}");
-}
-
-fn five() -> i32 {
-    5
+// The label can be any unit `cpc convert` knows, km or °F as well as m.
+fn print_labeled_measurement(value: f64, unit_label: &str) {
+    match Quantity::new(value, unit_label) {
+        Ok(measurement) => println!("The measurement is: {measurement}"),
+        Err(err) => println!("The measurement {value}{unit_label} is invalid: {err}"),
+    }
 }
 
-/* -  The 5 in five is the function's return value, which is why the return type
+/* -  five is generic now, see numeric, but it started out as
+ *
+ *        fn five() -> i32 {
+ *            5
+ *        }
+ *
+ *  - The 5 in five is the function's return value, which is why the return type
  *    is i32.
  *  - The line let x = five(); shows that we're using the return value of a func-
  *    tion to initialize a variable. Because the function five returns five ret-
@@ -124,11 +172,3 @@ fn five() -> i32 {
  *    but the body of the function is a lonely 5 with no semicolon because it's an
  *    expression whose value we want to return.
  */
-
-fn plus_one(x: i32) -> i32 {
-    x + 1
-}
-
-fn plus_two(x: i32) -> i32 {
-    x + 2
-}
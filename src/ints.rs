@@ -0,0 +1,296 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// - The twelve integer types from the data types lesson.
+// - isize and usize are treated as 64 bits wide, like on the machines this
+//   is normally run on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntType {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+}
+
+impl IntType {
+    pub const ALL: [IntType; 12] = [
+        IntType::I8,
+        IntType::I16,
+        IntType::I32,
+        IntType::I64,
+        IntType::I128,
+        IntType::Isize,
+        IntType::U8,
+        IntType::U16,
+        IntType::U32,
+        IntType::U64,
+        IntType::U128,
+        IntType::Usize,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::I128 => "i128",
+            IntType::Isize => "isize",
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::U128 => "u128",
+            IntType::Usize => "usize",
+        }
+    }
+
+    pub fn bits(self) -> u32 {
+        match self {
+            IntType::I8 | IntType::U8 => 8,
+            IntType::I16 | IntType::U16 => 16,
+            IntType::I32 | IntType::U32 => 32,
+            IntType::I64 | IntType::U64 | IntType::Isize | IntType::Usize => 64,
+            IntType::I128 | IntType::U128 => 128,
+        }
+    }
+
+    pub fn is_signed(self) -> bool {
+        matches!(
+            self,
+            IntType::I8
+                | IntType::I16
+                | IntType::I32
+                | IntType::I64
+                | IntType::I128
+                | IntType::Isize
+        )
+    }
+
+    // -(2^(n - 1)) for signed types, 0 for unsigned ones.
+    pub fn min(self) -> i128 {
+        if self.is_signed() {
+            i128::MIN >> (128 - self.bits())
+        } else {
+            0
+        }
+    }
+
+    // 2^(n - 1) - 1 for signed types, 2^n - 1 for unsigned ones.
+    pub fn max(self) -> u128 {
+        if self.is_signed() {
+            u128::MAX >> (129 - self.bits())
+        } else {
+            u128::MAX >> (128 - self.bits())
+        }
+    }
+
+    // The type of the same width with the other signedness: u8 for i8.
+    pub fn counterpart(self) -> IntType {
+        match self {
+            IntType::I8 => IntType::U8,
+            IntType::I16 => IntType::U16,
+            IntType::I32 => IntType::U32,
+            IntType::I64 => IntType::U64,
+            IntType::I128 => IntType::U128,
+            IntType::Isize => IntType::Usize,
+            IntType::U8 => IntType::I8,
+            IntType::U16 => IntType::I16,
+            IntType::U32 => IntType::I32,
+            IntType::U64 => IntType::I64,
+            IntType::U128 => IntType::I128,
+            IntType::Usize => IntType::Isize,
+        }
+    }
+
+    // The smallest type with the same signedness that can hold the value,
+    // falling back to the other signedness; what the compiler suggests.
+    pub fn smallest_fitting(
+        negative: bool,
+        magnitude: u128,
+        prefer_signed: bool,
+    ) -> Option<IntType> {
+        let fits = |ty: &IntType| IntValue::from_parts(*ty, negative, magnitude).is_some();
+        let by_size = |signed: bool| {
+            IntType::ALL.into_iter().filter(move |ty| {
+                ty.is_signed() == signed && !matches!(ty, IntType::Isize | IntType::Usize)
+            })
+        };
+
+        by_size(prefer_signed)
+            .find(fits)
+            .or_else(|| by_size(!prefer_signed).find(fits))
+    }
+}
+
+impl fmt::Display for IntType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownIntType(pub String);
+
+impl fmt::Display for UnknownIntType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not an integer type", self.0)
+    }
+}
+
+impl Error for UnknownIntType {}
+
+impl FromStr for IntType {
+    type Err = UnknownIntType;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        IntType::ALL
+            .into_iter()
+            .find(|ty| ty.name() == s)
+            .ok_or_else(|| UnknownIntType(s.to_string()))
+    }
+}
+
+// A value together with the integer type it has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntValue {
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    Isize(i64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Usize(u64),
+}
+
+impl IntValue {
+    pub fn ty(self) -> IntType {
+        match self {
+            IntValue::I8(_) => IntType::I8,
+            IntValue::I16(_) => IntType::I16,
+            IntValue::I32(_) => IntType::I32,
+            IntValue::I64(_) => IntType::I64,
+            IntValue::I128(_) => IntType::I128,
+            IntValue::Isize(_) => IntType::Isize,
+            IntValue::U8(_) => IntType::U8,
+            IntValue::U16(_) => IntType::U16,
+            IntValue::U32(_) => IntType::U32,
+            IntValue::U64(_) => IntType::U64,
+            IntValue::U128(_) => IntType::U128,
+            IntValue::Usize(_) => IntType::Usize,
+        }
+    }
+
+    // - Builds a value of type `ty` from a sign and a magnitude, so that
+    //   i8::MIN (a magnitude of 128) can be built even though 128 itself
+    //   doesn't fit in an i8.
+    // - Returns None when the value is out of range for the type.
+    pub fn from_parts(ty: IntType, negative: bool, magnitude: u128) -> Option<IntValue> {
+        if negative && magnitude != 0 {
+            let value = 0i128.checked_sub_unsigned(magnitude)?;
+            return IntValue::from_i128(ty, value);
+        }
+
+        IntValue::from_u128(ty, magnitude)
+    }
+
+    pub fn from_i128(ty: IntType, value: i128) -> Option<IntValue> {
+        Some(match ty {
+            IntType::I8 => IntValue::I8(value.try_into().ok()?),
+            IntType::I16 => IntValue::I16(value.try_into().ok()?),
+            IntType::I32 => IntValue::I32(value.try_into().ok()?),
+            IntType::I64 => IntValue::I64(value.try_into().ok()?),
+            IntType::I128 => IntValue::I128(value),
+            IntType::Isize => IntValue::Isize(value.try_into().ok()?),
+            IntType::U8 => IntValue::U8(value.try_into().ok()?),
+            IntType::U16 => IntValue::U16(value.try_into().ok()?),
+            IntType::U32 => IntValue::U32(value.try_into().ok()?),
+            IntType::U64 => IntValue::U64(value.try_into().ok()?),
+            IntType::U128 => IntValue::U128(value.try_into().ok()?),
+            IntType::Usize => IntValue::Usize(value.try_into().ok()?),
+        })
+    }
+
+    pub fn from_u128(ty: IntType, value: u128) -> Option<IntValue> {
+        Some(match ty {
+            IntType::I8 => IntValue::I8(value.try_into().ok()?),
+            IntType::I16 => IntValue::I16(value.try_into().ok()?),
+            IntType::I32 => IntValue::I32(value.try_into().ok()?),
+            IntType::I64 => IntValue::I64(value.try_into().ok()?),
+            IntType::I128 => IntValue::I128(value.try_into().ok()?),
+            IntType::Isize => IntValue::Isize(value.try_into().ok()?),
+            IntType::U8 => IntValue::U8(value.try_into().ok()?),
+            IntType::U16 => IntValue::U16(value.try_into().ok()?),
+            IntType::U32 => IntValue::U32(value.try_into().ok()?),
+            IntType::U64 => IntValue::U64(value.try_into().ok()?),
+            IntType::U128 => IntValue::U128(value),
+            IntType::Usize => IntValue::Usize(value.try_into().ok()?),
+        })
+    }
+
+    // The value's bits as they are stored in memory, two's complement for
+    // negative numbers, in the low `ty().bits()` bits of the result.
+    pub fn to_bits(self) -> u128 {
+        let mask = u128::MAX >> (128 - self.ty().bits());
+
+        let wide = match self {
+            IntValue::I8(v) => v as u128,
+            IntValue::I16(v) => v as u128,
+            IntValue::I32(v) => v as u128,
+            IntValue::I64(v) | IntValue::Isize(v) => v as u128,
+            IntValue::I128(v) => v as u128,
+            IntValue::U8(v) => v.into(),
+            IntValue::U16(v) => v.into(),
+            IntValue::U32(v) => v.into(),
+            IntValue::U64(v) | IntValue::Usize(v) => v.into(),
+            IntValue::U128(v) => v,
+        };
+
+        wide & mask
+    }
+
+    // The opposite of to_bits: reads the low `ty.bits()` bits as a `ty`,
+    // so 0b1111_1011 is 251 as a u8 and -5 as an i8.
+    pub fn from_bits(ty: IntType, bits: u128) -> IntValue {
+        let unused = 128 - ty.bits();
+
+        let value = if ty.is_signed() {
+            IntValue::from_i128(ty, ((bits << unused) as i128) >> unused)
+        } else {
+            IntValue::from_u128(ty, bits & (u128::MAX >> unused))
+        };
+
+        value.expect("the bits fit in the type")
+    }
+}
+
+impl fmt::Display for IntValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntValue::I8(v) => v.fmt(f),
+            IntValue::I16(v) => v.fmt(f),
+            IntValue::I32(v) => v.fmt(f),
+            IntValue::I64(v) | IntValue::Isize(v) => v.fmt(f),
+            IntValue::I128(v) => v.fmt(f),
+            IntValue::U8(v) => v.fmt(f),
+            IntValue::U16(v) => v.fmt(f),
+            IntValue::U32(v) => v.fmt(f),
+            IntValue::U64(v) | IntValue::Usize(v) => v.fmt(f),
+            IntValue::U128(v) => v.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::progress::Progress;
+use crate::{cflows, data_types, exercises, fibonnacci, funcs, variables};
+
+// - Every chapter file implements Lesson so that it can be plugged into the
+//   registry without touching the command line dispatcher.
+// - `prerequisites` lists the ids of the lessons that have to be completed
+//   before this one is allowed to run.
+pub trait Lesson {
+    fn id(&self) -> &'static str;
+
+    fn title(&self) -> &'static str;
+
+    fn summary(&self) -> &'static str;
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    // Shown in the lesson list; lessons that take arguments override this.
+    // Lessons that can be run in several ways put each form on its own line.
+    fn usage(&self) -> &'static str {
+        self.id()
+    }
+
+    fn run(&self, ctx: &mut Context) -> Result<(), LessonError>;
+}
+
+// - Context is what a lesson gets to work with while it runs: the arguments
+//   that followed its id on the command line and the learner's progress.
+pub struct Context {
+    args: Vec<String>,
+    progress: Progress,
+}
+
+impl Context {
+    pub fn new(args: Vec<String>, progress: Progress) -> Self {
+        Self { args, progress }
+    }
+
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    pub fn progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    pub fn progress_mut(&mut self) -> &mut Progress {
+        &mut self.progress
+    }
+
+    // For lessons that don't take any arguments at all.
+    pub fn expect_no_args(&self, id: &str) -> Result<(), LessonError> {
+        match self.args.first() {
+            None => Ok(()),
+            Some(arg) => Err(LessonError::InvalidArgs(format!(
+                "`{id}` takes no arguments, got `{arg}`"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum LessonError {
+    Unknown(String),
+    DuplicateId(&'static str),
+    UnknownPrerequisite {
+        lesson: &'static str,
+        prerequisite: &'static str,
+    },
+    MissingPrerequisites {
+        lesson: &'static str,
+        missing: Vec<&'static str>,
+    },
+    InvalidArgs(String),
+    Io(io::Error),
+}
+
+impl LessonError {
+    // Error for arguments that don't match any of the lesson's usage forms.
+    pub fn usage(usage: &str) -> Self {
+        let forms: Vec<String> = usage.lines().map(|form| format!("cpc {form}")).collect();
+
+        match forms.as_slice() {
+            [form] => LessonError::InvalidArgs(format!("usage: {form}")),
+            forms => LessonError::InvalidArgs(format!("usage:\n  {}", forms.join("\n  "))),
+        }
+    }
+}
+
+impl fmt::Display for LessonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LessonError::Unknown(id) => {
+                write!(f, "unknown lesson `{id}`, run `cpc lessons` to list them")
+            }
+            LessonError::DuplicateId(id) => write!(f, "lesson `{id}` is registered twice"),
+            LessonError::UnknownPrerequisite {
+                lesson,
+                prerequisite,
+            } => write!(
+                f,
+                "lesson `{lesson}` requires `{prerequisite}`, which isn't registered before it"
+            ),
+            LessonError::MissingPrerequisites { lesson, missing } => write!(
+                f,
+                "complete {} before starting `{lesson}`",
+                missing
+                    .iter()
+                    .map(|id| format!("`{id}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            LessonError::InvalidArgs(msg) => f.write_str(msg),
+            LessonError::Io(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for LessonError {}
+
+impl From<io::Error> for LessonError {
+    fn from(err: io::Error) -> Self {
+        LessonError::Io(err)
+    }
+}
+
+// - The registry keeps lessons in curriculum order, which is the order they
+//   were registered in.
+// - A lesson can only be registered after all of its prerequisites, so the
+//   curriculum order never asks for something that hasn't been taught yet.
+#[derive(Default)]
+pub struct Registry {
+    lessons: Vec<Box<dyn Lesson>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The lessons that ship with cpc.
+    pub fn curriculum() -> Self {
+        let mut registry = Self::new();
+
+        registry.register(variables::Variables);
+        registry.register(data_types::DataTypes);
+        registry.register(funcs::Functions);
+        registry.register(cflows::ControlFlow);
+        registry.register(fibonnacci::Fib);
+        registry.register(exercises::TwelveDays);
+        registry.register(exercises::NthFibonacci);
+        registry.register(exercises::Temperatures);
+
+        registry
+    }
+
+    // Panics if the lesson doesn't fit the curriculum, see `try_register`.
+    pub fn register(&mut self, lesson: impl Lesson + 'static) {
+        if let Err(err) = self.try_register(lesson) {
+            panic!("{err}");
+        }
+    }
+
+    pub fn try_register(&mut self, lesson: impl Lesson + 'static) -> Result<(), LessonError> {
+        if self.find(lesson.id()).is_some() {
+            return Err(LessonError::DuplicateId(lesson.id()));
+        }
+
+        if let Some(prerequisite) = lesson
+            .prerequisites()
+            .iter()
+            .find(|id| self.find(id).is_none())
+        {
+            return Err(LessonError::UnknownPrerequisite {
+                lesson: lesson.id(),
+                prerequisite,
+            });
+        }
+
+        self.lessons.push(Box::new(lesson));
+        Ok(())
+    }
+
+    pub fn lessons(&self) -> impl Iterator<Item = &dyn Lesson> {
+        self.lessons.iter().map(|lesson| lesson.as_ref())
+    }
+
+    pub fn find(&self, id: &str) -> Option<&dyn Lesson> {
+        self.lessons().find(|lesson| lesson.id() == id)
+    }
+
+    pub fn missing_prerequisites(
+        &self,
+        lesson: &dyn Lesson,
+        progress: &Progress,
+    ) -> Vec<&'static str> {
+        lesson
+            .prerequisites()
+            .iter()
+            .copied()
+            .filter(|id| !progress.is_completed(id))
+            .collect()
+    }
+
+    // Runs the lesson and records it as completed when it finishes without
+    // an error.
+    pub fn run(&self, id: &str, ctx: &mut Context) -> Result<(), LessonError> {
+        let lesson = self
+            .find(id)
+            .ok_or_else(|| LessonError::Unknown(id.to_string()))?;
+
+        let missing = self.missing_prerequisites(lesson, ctx.progress());
+        if !missing.is_empty() {
+            return Err(LessonError::MissingPrerequisites {
+                lesson: lesson.id(),
+                missing,
+            });
+        }
+
+        lesson.run(ctx)?;
+        ctx.progress_mut()
+            .mark_completed(lesson.id())
+            .map_err(|err| io::Error::new(err.kind(), format!("could not save progress: {err}")))?;
+
+        Ok(())
+    }
+}
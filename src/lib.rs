@@ -0,0 +1,32 @@
+// Each lesson lives in its own module and exposes a `run` entry point.
+// The `lessons` registry ties them together and `cli` turns the command line
+// into a call to one of them.
+pub mod batch;
+pub mod bignum;
+pub mod bits;
+pub mod cflows;
+pub mod character;
+pub mod chars;
+pub mod cli;
+pub mod data_types;
+pub mod decimal;
+pub mod destructure;
+pub mod eval;
+pub mod exercises;
+pub mod fibonnacci;
+pub mod floats;
+pub mod funcs;
+pub mod general_category;
+pub mod ints;
+pub mod lessons;
+pub mod literal;
+pub mod numeric;
+pub mod overflow;
+pub mod progress;
+pub mod rational;
+pub mod recurrence;
+pub mod scalar;
+pub mod temperature;
+pub mod units;
+pub mod variables;
+pub mod zeckendorf;
@@ -0,0 +1,255 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::cli::ToolResult;
+use crate::ints::{IntType, IntValue};
+
+// - An integer literal written the way data_types.rs writes them:
+//     98_222    0xff    0o77    0b1111_0000    b'A'
+//   optionally followed by a type suffix such as 57u8 or 1_000i64.
+// - Without a suffix the literal is an i32, the type the compiler falls back
+//   to when nothing else decides it; byte literals are always u8.
+// - A leading - is accepted and applied to the value, the way -5 is really
+//   the negation of the literal 5.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Literal {
+    pub value: IntValue,
+    pub radix: u32,
+    pub suffix: Option<IntType>,
+    pub byte: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiteralError {
+    Empty,
+    NoDigits,
+    InvalidDigit {
+        digit: char,
+        radix: u32,
+    },
+    UnknownSuffix(String),
+    NegativeUnsigned(IntType),
+    OutOfRange {
+        literal: String,
+        ty: IntType,
+        suggestion: Option<IntType>,
+    },
+    InvalidByte(String),
+    MismatchedTypes {
+        expected: IntType,
+        found: IntType,
+    },
+}
+
+impl fmt::Display for LiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LiteralError::Empty => f.write_str("expected an integer literal, found nothing"),
+            LiteralError::NoDigits => f.write_str("no valid digits found for number"),
+            LiteralError::InvalidDigit { digit, radix } => {
+                write!(f, "invalid digit `{digit}` for a base {radix} literal")
+            }
+            LiteralError::UnknownSuffix(suffix) => write!(
+                f,
+                "invalid suffix `{suffix}` for number literal, the suffix must be one of the \
+                 integer types (`u8`, `i32`, `usize`, etc.)"
+            ),
+            LiteralError::NegativeUnsigned(ty) => {
+                write!(f, "cannot apply unary operator `-` to type `{ty}`")
+            }
+            LiteralError::OutOfRange {
+                literal,
+                ty,
+                suggestion,
+            } => {
+                write!(
+                    f,
+                    "literal out of range for `{ty}`: `{literal}` does not fit into the type \
+                     `{ty}` whose range is `{}..={}`",
+                    ty.min(),
+                    ty.max()
+                )?;
+
+                match suggestion {
+                    Some(suggestion) => {
+                        write!(f, "; consider using the type `{suggestion}` instead")
+                    }
+                    None => Ok(()),
+                }
+            }
+            LiteralError::InvalidByte(text) => write!(f, "`{text}` is not a valid byte literal"),
+            LiteralError::MismatchedTypes { expected, found } => {
+                write!(
+                    f,
+                    "mismatched types: expected `{expected}`, found `{found}`"
+                )
+            }
+        }
+    }
+}
+
+impl Error for LiteralError {}
+
+pub fn parse_literal(text: &str) -> Result<Literal, LiteralError> {
+    parse(text, IntType::I32)
+}
+
+// A literal that has to end up as a `ty`, the way the 200 in
+// `let x: u8 = 200;` is a u8 without needing a suffix.
+pub fn parse_literal_as(text: &str, ty: IntType) -> Result<IntValue, LiteralError> {
+    let literal = parse(text, ty)?;
+
+    let found = literal.value.ty();
+    if found != ty {
+        return Err(LiteralError::MismatchedTypes {
+            expected: ty,
+            found,
+        });
+    }
+
+    Ok(literal.value)
+}
+
+fn parse(text: &str, default: IntType) -> Result<Literal, LiteralError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(LiteralError::Empty);
+    }
+
+    let (negative, body) = match text.strip_prefix('-') {
+        Some(body) => (true, body.trim_start()),
+        None => (false, text),
+    };
+
+    if body.starts_with("b'") {
+        if negative {
+            return Err(LiteralError::NegativeUnsigned(IntType::U8));
+        }
+
+        return Ok(Literal {
+            value: IntValue::U8(parse_byte(body)?),
+            radix: 10,
+            suffix: None,
+            byte: true,
+        });
+    }
+
+    let (radix, rest) = match body.get(..2) {
+        Some("0x") => (16, &body[2..]),
+        Some("0o") => (8, &body[2..]),
+        Some("0b") => (2, &body[2..]),
+        _ => (10, body),
+    };
+
+    // i and u aren't digits in any base, so the suffix starts at the first
+    // one of them.
+    let (digits, suffix) = match rest.find(['i', 'u']) {
+        Some(at) => {
+            let suffix = &rest[at..];
+            let ty = suffix
+                .parse::<IntType>()
+                .map_err(|_| LiteralError::UnknownSuffix(suffix.to_string()))?;
+            (&rest[..at], Some(ty))
+        }
+        None => (rest, None),
+    };
+
+    // A decimal literal can't start with _, that would be an identifier.
+    if radix == 10 && digits.starts_with('_') {
+        return Err(LiteralError::InvalidDigit { digit: '_', radix });
+    }
+
+    let ty = suffix.unwrap_or(default);
+    let out_of_range = |magnitude: Option<u128>| LiteralError::OutOfRange {
+        literal: text.to_string(),
+        ty,
+        suggestion: magnitude
+            .and_then(|magnitude| IntType::smallest_fitting(negative, magnitude, ty.is_signed())),
+    };
+
+    let mut magnitude: Option<u128> = None;
+    for c in digits.chars().filter(|&c| c != '_') {
+        let digit = c
+            .to_digit(radix)
+            .ok_or(LiteralError::InvalidDigit { digit: c, radix })?;
+
+        magnitude = Some(
+            magnitude
+                .unwrap_or(0)
+                .checked_mul(radix.into())
+                .and_then(|value| value.checked_add(digit.into()))
+                .ok_or_else(|| out_of_range(None))?,
+        );
+    }
+    let magnitude = magnitude.ok_or(LiteralError::NoDigits)?;
+
+    if negative && !ty.is_signed() {
+        return Err(LiteralError::NegativeUnsigned(ty));
+    }
+
+    let value = IntValue::from_parts(ty, negative, magnitude)
+        .ok_or_else(|| out_of_range(Some(magnitude)))?;
+
+    Ok(Literal {
+        value,
+        radix,
+        suffix,
+        byte: false,
+    })
+}
+
+// b'A', plus the escapes a byte literal allows: \n \r \t \\ \' \" \0 and \xHH.
+fn parse_byte(text: &str) -> Result<u8, LiteralError> {
+    let invalid = || LiteralError::InvalidByte(text.to_string());
+
+    let inner = text
+        .strip_prefix("b'")
+        .and_then(|rest| rest.strip_suffix('\''))
+        .ok_or_else(invalid)?;
+
+    let byte = match inner.as_bytes() {
+        [b'\\', b'n'] => b'\n',
+        [b'\\', b'r'] => b'\r',
+        [b'\\', b't'] => b'\t',
+        [b'\\', b'\\'] => b'\\',
+        [b'\\', b'\''] => b'\'',
+        [b'\\', b'"'] => b'"',
+        [b'\\', b'0'] => b'\0',
+        [b'\\', b'x', high, low] => {
+            let hex = std::str::from_utf8(&[*high, *low])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            hex.ok_or_else(invalid)?
+        }
+        [byte] if byte.is_ascii() && *byte != b'\'' && *byte != b'\\' => *byte,
+        _ => return Err(invalid()),
+    };
+
+    Ok(byte)
+}
+
+pub fn literal_command(args: &[String]) -> ToolResult {
+    let [text] = args else {
+        return Err("usage: cpc literal <literal>".into());
+    };
+
+    let literal = parse_literal(text)?;
+
+    let ty = match (literal.byte, literal.suffix) {
+        (true, _) => String::from("u8 (byte literal)"),
+        (false, Some(ty)) => format!("{ty} (from the suffix)"),
+        (false, None) => format!("{} (no suffix, the default)", literal.value.ty()),
+    };
+    let radix = match literal.radix {
+        2 => "binary",
+        8 => "octal",
+        16 => "hexadecimal",
+        _ => "decimal",
+    };
+
+    println!("{}", text.trim());
+    println!("  type:  {ty}");
+    println!("  radix: {radix}");
+    println!("  value: {}", literal.value);
+    Ok(())
+}
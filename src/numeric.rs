@@ -0,0 +1,168 @@
+use std::fmt;
+use std::ops::Add;
+
+// - Generic versions of the plus_one, plus_two and five functions from the
+//   functions lesson, which only took an i32 and panicked in a debug build
+//   when given i32::MAX.
+// - `Number` is implemented for every integer and float type, and add_n
+//   and its checked and saturating variants work on all of them.
+// - Trait methods can't be const fn yet, so `consts` has a const fn copy of
+//   every helper for each type: consts::u8::plus_one(254) works in a const.
+
+pub trait Number: Copy + PartialOrd + fmt::Debug + fmt::Display + Add<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    // None when n doesn't fit, 200 as an i8.
+    fn from_u8(n: u8) -> Option<Self>;
+
+    // - None when the sum doesn't fit.
+    // - Floats don't overflow, they become infinite, so a sum that isn't
+    //   finite counts as not fitting.
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    // The sum, or MIN or MAX when it doesn't fit. NaN stays NaN.
+    fn saturating_add(self, other: Self) -> Self;
+}
+
+macro_rules! integers {
+    ($($t:ident)*) => {$(
+        impl Number for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const MIN: Self = $t::MIN;
+            const MAX: Self = $t::MAX;
+
+            fn from_u8(n: u8) -> Option<Self> {
+                $t::try_from(n).ok()
+            }
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                $t::checked_add(self, other)
+            }
+
+            fn saturating_add(self, other: Self) -> Self {
+                $t::saturating_add(self, other)
+            }
+        }
+    )*};
+}
+
+macro_rules! floats {
+    ($($t:ident)*) => {$(
+        impl Number for $t {
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+            const MIN: Self = $t::MIN;
+            const MAX: Self = $t::MAX;
+
+            fn from_u8(n: u8) -> Option<Self> {
+                Some($t::from(n))
+            }
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                let sum = self + other;
+                sum.is_finite().then_some(sum)
+            }
+
+            fn saturating_add(self, other: Self) -> Self {
+                (self + other).clamp($t::MIN, $t::MAX)
+            }
+        }
+    )*};
+}
+
+integers!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+floats!(f32 f64);
+
+// x + n, which panics on overflow in a debug build and wraps in a release
+// one, just like + does.
+pub fn add_n<T: Number>(x: T, n: T) -> T {
+    x + n
+}
+
+pub fn checked_add_n<T: Number>(x: T, n: T) -> Option<T> {
+    x.checked_add(n)
+}
+
+pub fn saturating_add_n<T: Number>(x: T, n: T) -> T {
+    x.saturating_add(n)
+}
+
+pub fn plus_one<T: Number>(x: T) -> T {
+    add_n(x, T::ONE)
+}
+
+pub fn plus_two<T: Number>(x: T) -> T {
+    add_n(x, small(2))
+}
+
+// 5 as whichever type the caller needs: let x: u8 = five();
+pub fn five<T: Number>() -> T {
+    small(5)
+}
+
+fn small<T: Number>(n: u8) -> T {
+    T::from_u8(n).expect("every number type holds the numbers up to 127")
+}
+
+// - One module per type, named after it, with the helpers as const fns.
+// - Overflow in a const is a compile error, in a const fn called at
+//   runtime it's the same as add_n.
+pub mod consts {
+    macro_rules! helpers {
+        (@common $t:ident) => {
+            pub const fn add_n(x: $t, n: $t) -> $t {
+                x + n
+            }
+
+            pub const fn plus_one(x: $t) -> $t {
+                add_n(x, 1 as $t)
+            }
+
+            pub const fn plus_two(x: $t) -> $t {
+                add_n(x, 2 as $t)
+            }
+
+            pub const fn five() -> $t {
+                5 as $t
+            }
+        };
+        (integers $($t:ident)*) => {$(
+            pub mod $t {
+                helpers!(@common $t);
+
+                pub const fn checked_add_n(x: $t, n: $t) -> Option<$t> {
+                    x.checked_add(n)
+                }
+
+                pub const fn saturating_add_n(x: $t, n: $t) -> $t {
+                    x.saturating_add(n)
+                }
+            }
+        )*};
+        (floats $($t:ident)*) => {$(
+            pub mod $t {
+                helpers!(@common $t);
+
+                pub const fn checked_add_n(x: $t, n: $t) -> Option<$t> {
+                    let sum = x + n;
+                    if sum.is_finite() {
+                        Some(sum)
+                    } else {
+                        None
+                    }
+                }
+
+                pub const fn saturating_add_n(x: $t, n: $t) -> $t {
+                    (x + n).clamp($t::MIN, $t::MAX)
+                }
+            }
+        )*};
+    }
+
+    helpers!(integers i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+    helpers!(floats f32 f64);
+}
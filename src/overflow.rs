@@ -0,0 +1,462 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::cli::ToolResult;
+use crate::ints::{IntType, IntValue};
+use crate::literal::{parse_literal, parse_literal_as, LiteralError};
+
+// - The operations that can overflow. Every one of them comes in the
+//   wrapping_, checked_, saturating_ and overflowing_ flavours, except that
+//   there's no saturating_rem, saturating_shl or saturating_shr.
+// - pow, shl and shr take a u32 on the right, whatever the type on the left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+    Shl,
+    Shr,
+}
+
+impl Op {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            Op::Rem => "%",
+            Op::Pow => "pow",
+            Op::Shl => "<<",
+            Op::Shr => ">>",
+        }
+    }
+
+    // The suffix of the std methods: add in wrapping_add.
+    pub fn method(self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Sub => "sub",
+            Op::Mul => "mul",
+            Op::Div => "div",
+            Op::Rem => "rem",
+            Op::Pow => "pow",
+            Op::Shl => "shl",
+            Op::Shr => "shr",
+        }
+    }
+
+    pub fn rhs_type(self, ty: IntType) -> IntType {
+        match self {
+            Op::Pow | Op::Shl | Op::Shr => IntType::U32,
+            _ => ty,
+        }
+    }
+
+    // What a debug build panics with when the plain operator overflows.
+    pub fn overflow_message(self) -> &'static str {
+        match self {
+            Op::Add => "attempt to add with overflow",
+            Op::Sub => "attempt to subtract with overflow",
+            Op::Mul | Op::Pow => "attempt to multiply with overflow",
+            Op::Div => "attempt to divide with overflow",
+            Op::Rem => "attempt to calculate the remainder with overflow",
+            Op::Shl => "attempt to shift left with overflow",
+            Op::Shr => "attempt to shift right with overflow",
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+impl FromStr for Op {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "+" | "add" => Ok(Op::Add),
+            "-" | "sub" => Ok(Op::Sub),
+            "*" | "x" | "mul" => Ok(Op::Mul),
+            "/" | "div" => Ok(Op::Div),
+            "%" | "rem" => Ok(Op::Rem),
+            "**" | "pow" => Ok(Op::Pow),
+            "<<" | "shl" => Ok(Op::Shl),
+            ">>" | "shr" => Ok(Op::Shr),
+            other => Err(format!(
+                "unknown operator `{other}`, expected one of + - * / % pow << >>"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Wrapping,
+    Checked,
+    Saturating,
+    Overflowing,
+}
+
+impl Mode {
+    pub const ALL: [Mode; 4] = [
+        Mode::Wrapping,
+        Mode::Checked,
+        Mode::Saturating,
+        Mode::Overflowing,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Wrapping => "wrapping",
+            Mode::Checked => "checked",
+            Mode::Saturating => "saturating",
+            Mode::Overflowing => "overflowing",
+        }
+    }
+}
+
+// What one of the methods returns, written the way {:?} would show it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Value(IntValue),
+    Checked(Option<IntValue>),
+    Overflowing(IntValue, bool),
+    // Dividing by zero panics in every mode but checked.
+    Panic(&'static str),
+    // std has no such method.
+    Missing,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Value(value) => write!(f, "{value}"),
+            Outcome::Checked(Some(value)) => write!(f, "Some({value})"),
+            Outcome::Checked(None) => f.write_str("None"),
+            Outcome::Overflowing(value, overflowed) => write!(f, "({value}, {overflowed})"),
+            Outcome::Panic(message) => write!(f, "panics: {message}"),
+            Outcome::Missing => f.write_str("-"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Results {
+    pub wrapping: Outcome,
+    pub checked: Outcome,
+    pub saturating: Outcome,
+    pub overflowing: Outcome,
+}
+
+impl Results {
+    pub fn get(&self, mode: Mode) -> Outcome {
+        match mode {
+            Mode::Wrapping => self.wrapping,
+            Mode::Checked => self.checked,
+            Mode::Saturating => self.saturating,
+            Mode::Overflowing => self.overflowing,
+        }
+    }
+
+    pub fn overflowed(&self) -> bool {
+        matches!(self.overflowing, Outcome::Overflowing(_, true))
+    }
+}
+
+// - `b` is read back from its bits as a `$t`, or as the u32 that pow and
+//   the shifts take, so every arm can call the std methods on the real type.
+// - isize and usize are worked out as i64 and u64; building the results
+//   from `$ty` gives them their own type back.
+macro_rules! results {
+    ($t:ty, $a:expr, $ty:expr, $b_bits:expr, $op:expr) => {{
+        let a: $t = $a;
+        let b = $b_bits as $t;
+        let n = $b_bits as u32;
+        let value = |x: $t| IntValue::from_bits($ty, x as u128);
+
+        let (wrapping, checked, saturating, overflowing) = match $op {
+            Op::Add => (
+                a.wrapping_add(b),
+                a.checked_add(b),
+                Some(a.saturating_add(b)),
+                a.overflowing_add(b),
+            ),
+            Op::Sub => (
+                a.wrapping_sub(b),
+                a.checked_sub(b),
+                Some(a.saturating_sub(b)),
+                a.overflowing_sub(b),
+            ),
+            Op::Mul => (
+                a.wrapping_mul(b),
+                a.checked_mul(b),
+                Some(a.saturating_mul(b)),
+                a.overflowing_mul(b),
+            ),
+            Op::Div => (
+                a.wrapping_div(b),
+                a.checked_div(b),
+                Some(a.saturating_div(b)),
+                a.overflowing_div(b),
+            ),
+            Op::Rem => (
+                a.wrapping_rem(b),
+                a.checked_rem(b),
+                None,
+                a.overflowing_rem(b),
+            ),
+            Op::Pow => (
+                a.wrapping_pow(n),
+                a.checked_pow(n),
+                Some(a.saturating_pow(n)),
+                a.overflowing_pow(n),
+            ),
+            Op::Shl => (
+                a.wrapping_shl(n),
+                a.checked_shl(n),
+                None,
+                a.overflowing_shl(n),
+            ),
+            Op::Shr => (
+                a.wrapping_shr(n),
+                a.checked_shr(n),
+                None,
+                a.overflowing_shr(n),
+            ),
+        };
+
+        Results {
+            wrapping: Outcome::Value(value(wrapping)),
+            checked: Outcome::Checked(checked.map(value)),
+            saturating: saturating.map_or(Outcome::Missing, |x| Outcome::Value(value(x))),
+            overflowing: Outcome::Overflowing(value(overflowing.0), overflowing.1),
+        }
+    }};
+}
+
+// - Evaluates `a op b` in all four modes, in the type of `a`.
+// - `b` has to be of `op.rhs_type(a.ty())`: the same type as `a`, or a u32
+//   for pow and the shifts.
+pub fn evaluate(a: IntValue, op: Op, b: IntValue) -> Results {
+    let ty = a.ty();
+    assert_eq!(
+        b.ty(),
+        op.rhs_type(ty),
+        "the right-hand side has the wrong type"
+    );
+
+    if matches!(op, Op::Div | Op::Rem) && b.to_bits() == 0 {
+        let message = match op {
+            Op::Div => "attempt to divide by zero",
+            _ => "attempt to calculate the remainder with a divisor of zero",
+        };
+        return Results {
+            wrapping: Outcome::Panic(message),
+            checked: Outcome::Checked(None),
+            saturating: if op == Op::Div {
+                Outcome::Panic(message)
+            } else {
+                Outcome::Missing
+            },
+            overflowing: Outcome::Panic(message),
+        };
+    }
+
+    let b = b.to_bits();
+    match a {
+        IntValue::I8(a) => results!(i8, a, ty, b, op),
+        IntValue::I16(a) => results!(i16, a, ty, b, op),
+        IntValue::I32(a) => results!(i32, a, ty, b, op),
+        IntValue::I64(a) | IntValue::Isize(a) => results!(i64, a, ty, b, op),
+        IntValue::I128(a) => results!(i128, a, ty, b, op),
+        IntValue::U8(a) => results!(u8, a, ty, b, op),
+        IntValue::U16(a) => results!(u16, a, ty, b, op),
+        IntValue::U32(a) => results!(u32, a, ty, b, op),
+        IntValue::U64(a) | IntValue::Usize(a) => results!(u64, a, ty, b, op),
+        IntValue::U128(a) => results!(u128, a, ty, b, op),
+    }
+}
+
+fn expression(a: &str, op: Op, b: &str) -> String {
+    match op {
+        Op::Pow if a.starts_with('-') => format!("({a}).pow({b})"),
+        Op::Pow => format!("{a}.pow({b})"),
+        _ => format!("{a} {op} {b}"),
+    }
+}
+
+fn operands(a: &str, op: Op, b: &str, ty: IntType) -> Result<(IntValue, IntValue), LiteralError> {
+    Ok((
+        parse_literal_as(a, ty)?,
+        parse_literal_as(b, op.rhs_type(ty))?,
+    ))
+}
+
+// - What the plain operator does with `a op b`, given what `evaluate`
+//   returned for it.
+// - Division and remainder overflow, MIN / -1, is checked in every build,
+//   like dividing by zero; only the other operators wrap in release builds.
+pub fn explain(a: &str, op: Op, b: &str, results: &Results) -> String {
+    let expression = expression(a, op, b);
+    let ty = match results.wrapping {
+        Outcome::Value(value) => value.ty(),
+        Outcome::Panic(message) => {
+            return format!("{expression} panics with \"{message}\" in every build.")
+        }
+        outcome => unreachable!("wrapping methods return a value, not {outcome}"),
+    };
+
+    if !results.overflowed() {
+        return format!(
+            "{expression} = {} fits in {ty}, so every mode agrees.",
+            results.wrapping
+        );
+    }
+
+    let overflows = format!(
+        "{expression} overflows {ty}, whose range is {}..={}: a plain {} panics with",
+        ty.min(),
+        ty.max(),
+        op.symbol()
+    );
+    match op {
+        Op::Div | Op::Rem => format!(
+            "{overflows}\n\"{}\" in every build, division overflow is always checked.",
+            op.overflow_message()
+        ),
+        _ => format!(
+            "{overflows}\n\"{}\" in debug builds and gives {} in release builds.",
+            op.overflow_message(),
+            results.wrapping
+        ),
+    }
+}
+
+fn print_results(a: &str, op: Op, b: &str, ty: IntType) -> ToolResult {
+    let (x, y) = operands(a, op, b, ty)?;
+    let results = evaluate(x, op, y);
+    let expression = expression(a, op, b);
+
+    println!("{expression} as {ty}");
+    for mode in Mode::ALL {
+        let label = format!("{}_{}:", mode.name(), op.method());
+        match results.get(mode) {
+            Outcome::Missing => println!("  {label:<18}not in std"),
+            outcome => println!("  {label:<18}{outcome}"),
+        }
+    }
+    println!();
+
+    println!("{}", explain(a, op, b, &results));
+
+    Ok(())
+}
+
+// One row per integer type, one column per mode.
+fn print_table(a: &str, op: Op, b: &str) -> ToolResult {
+    let mut header = vec![String::from("type")];
+    header.extend(
+        Mode::ALL
+            .iter()
+            .map(|mode| format!("{}_{}", mode.name(), op.method())),
+    );
+
+    let mut rows = vec![header];
+    for ty in IntType::ALL {
+        let mut row = vec![ty.to_string()];
+
+        match operands(a, op, b, ty) {
+            Ok((x, y)) => {
+                let results = evaluate(x, op, y);
+                row.extend(Mode::ALL.iter().map(|&mode| results.get(mode).to_string()));
+            }
+            Err(LiteralError::OutOfRange { .. }) => row.push(String::from("out of range")),
+            Err(LiteralError::NegativeUnsigned(_)) => row.push(String::from("negative")),
+            Err(LiteralError::MismatchedTypes { found, .. }) => {
+                row.push(format!("suffix is {found}"))
+            }
+            Err(err) => return Err(err.into()),
+        }
+
+        rows.push(row);
+    }
+
+    let widths: Vec<usize> = (0..=Mode::ALL.len())
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    println!("{}", expression(a, op, b));
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:width$}"))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+
+    Ok(())
+}
+
+// - cpc overflow 200 + 100 --type u8 shows what each mode gives in a u8.
+// - Without --type the suffix of either operand picks the type, falling back
+//   to i32 the way the compiler does.
+// - --table evaluates the operation in every integer type instead.
+pub fn overflow_command(args: &[String]) -> ToolResult {
+    const USAGE: &str = "usage: cpc overflow <a> <op> <b> [--type <type> | --table]";
+
+    let mut positional = Vec::new();
+    let mut ty = None;
+    let mut table = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--type" => {
+                let name = args.next().ok_or("`--type` needs a value")?;
+                ty = Some(name.parse::<IntType>()?);
+            }
+            "--table" => table = true,
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let [a, op, b] = positional[..] else {
+        return Err(USAGE.into());
+    };
+    let op: Op = op.parse()?;
+
+    if table {
+        if ty.is_some() {
+            return Err(USAGE.into());
+        }
+        return print_table(a, op, b);
+    }
+
+    let ty = match ty {
+        Some(ty) => ty,
+        None => {
+            let suffix = parse_literal(a)?.suffix;
+            let rhs_suffix = match op.rhs_type(IntType::I32) {
+                IntType::U32 => None,
+                _ => parse_literal(b)?.suffix,
+            };
+            suffix.or(rhs_suffix).unwrap_or(IntType::I32)
+        }
+    };
+
+    print_results(a, op, b, ty)
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+// - Progress remembers which lessons have been completed between runs of cpc.
+// - It is stored as a plain text file with one lesson id per line, so it can
+//   be inspected or edited by hand.
+// - When no location can be found (no CPC_PROGRESS and no HOME), progress is
+//   only kept in memory for the current run.
+#[derive(Debug, Default)]
+pub struct Progress {
+    completed: BTreeSet<String>,
+    path: Option<PathBuf>,
+}
+
+impl Progress {
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    // CPC_PROGRESS wins over the default location of ~/.cpc/progress.
+    pub fn default_path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os("CPC_PROGRESS") {
+            return Some(PathBuf::from(path));
+        }
+
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cpc").join("progress"))
+    }
+
+    pub fn load(path: PathBuf) -> io::Result<Self> {
+        let completed = match fs::read_to_string(&path) {
+            Ok(contents) => contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeSet::new(),
+            Err(err) => return Err(err),
+        };
+
+        Ok(Self {
+            completed,
+            path: Some(path),
+        })
+    }
+
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.completed.contains(id)
+    }
+
+    pub fn completed(&self) -> impl Iterator<Item = &str> {
+        self.completed.iter().map(String::as_str)
+    }
+
+    // Returns true when the lesson wasn't already marked as completed.
+    pub fn mark_completed(&mut self, id: &str) -> io::Result<bool> {
+        if !self.completed.insert(id.to_string()) {
+            return Ok(false);
+        }
+
+        self.save()?;
+        Ok(true)
+    }
+
+    pub fn reset(&mut self) -> io::Result<()> {
+        self.completed.clear();
+        self.save()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut contents = String::new();
+        for id in &self.completed {
+            contents.push_str(id);
+            contents.push('\n');
+        }
+
+        fs::write(path, contents)
+    }
+}
@@ -0,0 +1,291 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+// - An exact fraction num / den, always reduced and with a positive
+//   denominator, so equal values compare equal field by field.
+// - Unlike Decimal it can divide: 5/9 stays 5/9 instead of becoming
+//   0.5555.., which is what lets temperature conversions round-trip.
+// - The operators panic on overflow the way integer arithmetic does in a
+//   debug build; the checked_ methods return None instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+const fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational::integer(0);
+    pub const ONE: Rational = Rational::integer(1);
+
+    pub const fn new(num: i128, den: i128) -> Rational {
+        assert!(den != 0, "a fraction can't have a denominator of zero");
+        let divisor = gcd(num, den);
+        let divisor = if divisor == 0 { 1 } else { divisor };
+        let divisor = if den < 0 { -divisor } else { divisor };
+        Rational {
+            num: num / divisor,
+            den: den / divisor,
+        }
+    }
+
+    pub const fn integer(n: i128) -> Rational {
+        Rational { num: n, den: 1 }
+    }
+
+    pub fn numer(self) -> i128 {
+        self.num
+    }
+
+    pub fn denom(self) -> i128 {
+        self.den
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.num < 0
+    }
+
+    pub fn checked_add(self, other: Rational) -> Option<Rational> {
+        let num = self
+            .num
+            .checked_mul(other.den)?
+            .checked_add(other.num.checked_mul(self.den)?)?;
+        Some(Rational::new(num, self.den.checked_mul(other.den)?))
+    }
+
+    pub fn checked_sub(self, other: Rational) -> Option<Rational> {
+        self.checked_add(-other)
+    }
+
+    pub fn checked_mul(self, other: Rational) -> Option<Rational> {
+        // Cross-reducing first keeps the intermediate products small.
+        let a = gcd(self.num, other.den).max(1);
+        let b = gcd(other.num, self.den).max(1);
+        let num = (self.num / a).checked_mul(other.num / b)?;
+        let den = (self.den / b).checked_mul(other.den / a)?;
+        Some(Rational::new(num, den))
+    }
+
+    // None for overflow and for dividing by zero.
+    pub fn checked_div(self, other: Rational) -> Option<Rational> {
+        if other.num == 0 {
+            return None;
+        }
+        self.checked_mul(Rational::new(other.den, other.num))
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    // - The value rounded to `places` decimal places, halves away from
+    //   zero, without trailing zeros: 5/9 to 2 places is 0.56, 1/2 is 0.5.
+    // - Exact values that need no more places are printed in full.
+    // - Worked out by long division one digit at a time, so it can't
+    //   overflow however large the numerator or denominator is.
+    pub fn to_decimal(self, places: u32) -> String {
+        let den = self.den.unsigned_abs();
+        let mut whole = self.num.unsigned_abs() / den;
+        let mut rem = self.num.unsigned_abs() % den;
+
+        let mut digits = Vec::with_capacity(places as usize);
+        for _ in 0..places {
+            // rem × 10 / den, by adding rem ten times; every partial sum
+            // stays below 2 × den, which always fits in a u128.
+            let mut digit = 0u8;
+            let mut next = 0u128;
+            for _ in 0..10 {
+                next += rem;
+                if next >= den {
+                    next -= den;
+                    digit += 1;
+                }
+            }
+            digits.push(digit);
+            rem = next;
+        }
+
+        // Round half away from zero, carrying into the whole part.
+        if rem >= den - rem {
+            let mut carry = true;
+            for digit in digits.iter_mut().rev() {
+                if *digit == 9 {
+                    *digit = 0;
+                } else {
+                    *digit += 1;
+                    carry = false;
+                    break;
+                }
+            }
+            if carry {
+                whole += 1;
+            }
+        }
+
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        let zero = whole == 0 && digits.is_empty();
+        let sign = if self.num < 0 && !zero { "-" } else { "" };
+        if digits.is_empty() {
+            return format!("{sign}{whole}");
+        }
+        let fraction: String = digits
+            .iter()
+            .map(|digit| char::from(b'0' + digit))
+            .collect();
+        format!("{sign}{whole}.{fraction}")
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The denominators are positive, so cross-multiplying keeps the order.
+        match (
+            self.num.checked_mul(other.den),
+            other.num.checked_mul(self.den),
+        ) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => self.to_f64().total_cmp(&other.to_f64()),
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+macro_rules! operator {
+    ($trait:ident, $method:ident, $checked:ident, $message:literal) => {
+        impl $trait for Rational {
+            type Output = Rational;
+
+            fn $method(self, other: Rational) -> Rational {
+                self.$checked(other).expect($message)
+            }
+        }
+    };
+}
+
+operator!(Add, add, checked_add, "attempt to add with overflow");
+operator!(Sub, sub, checked_sub, "attempt to subtract with overflow");
+operator!(Mul, mul, checked_mul, "attempt to multiply with overflow");
+operator!(
+    Div,
+    div,
+    checked_div,
+    "attempt to divide by zero or with overflow"
+);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRationalError {
+    Empty,
+    Invalid(String),
+    TooLarge(String),
+}
+
+impl fmt::Display for ParseRationalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseRationalError::Empty => f.write_str("expected a number, found nothing"),
+            ParseRationalError::Invalid(text) => write!(f, "`{text}` is not a number"),
+            ParseRationalError::TooLarge(text) => {
+                write!(f, "`{text}` has too many digits to be worked with exactly")
+            }
+        }
+    }
+}
+
+impl Error for ParseRationalError {}
+
+impl FromStr for Rational {
+    type Err = ParseRationalError;
+
+    // A decimal number like Decimal reads them, [-]digits[.digits][e[-]digits],
+    // or a fraction written as 5/9.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        if text.is_empty() {
+            return Err(ParseRationalError::Empty);
+        }
+        let invalid = || ParseRationalError::Invalid(text.to_string());
+        let too_large = || ParseRationalError::TooLarge(text.to_string());
+
+        if let Some((num, den)) = text.split_once('/') {
+            let num: Rational = num.parse()?;
+            let den: Rational = den.parse()?;
+            return num.checked_div(den).ok_or_else(invalid);
+        }
+
+        let cleaned = text.replace('_', "");
+        let (negative, unsigned) = match cleaned.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, cleaned.strip_prefix('+').unwrap_or(&cleaned)),
+        };
+
+        let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+            Some((mantissa, exponent)) => {
+                let exponent: i32 = exponent.parse().map_err(|_| invalid())?;
+                (mantissa, exponent)
+            }
+            None => (unsigned, 0),
+        };
+
+        let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let digits = format!("{whole}{fraction}");
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let num: i128 = digits.parse().map_err(|_| too_large())?;
+        let num = if negative { -num } else { num };
+        let scale = exponent - fraction.len() as i32;
+        let power = 10i128
+            .checked_pow(scale.unsigned_abs())
+            .ok_or_else(too_large)?;
+
+        if scale >= 0 {
+            Ok(Rational::integer(
+                num.checked_mul(power).ok_or_else(too_large)?,
+            ))
+        } else {
+            Ok(Rational::new(num, power))
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::bignum::BigUint;
+use crate::cli::ToolResult;
+
+// - A linear recurrence builds every term out of the k terms before it:
+//     a(n) = c1 * a(n - 1) + c2 * a(n - 2) + ... + ck * a(n - k)
+//   starting from the k seeds a(0), ..., a(k - 1).
+// - Fibonnacci is coefficients 1, 1 with seeds 0, 1; Lucas keeps the
+//   coefficients and changes the seeds to 2, 1.
+// - Coefficients and seeds are non-negative, so every term is a BigUint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinearRecurrence {
+    coefficients: Vec<u64>,
+    seeds: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Evaluation {
+    #[default]
+    Iterative,
+    Matrix,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecurrenceError {
+    Empty,
+    LengthMismatch { coefficients: usize, seeds: usize },
+    UnknownSequence(String),
+}
+
+impl fmt::Display for RecurrenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecurrenceError::Empty => f.write_str("a recurrence needs at least one coefficient"),
+            RecurrenceError::LengthMismatch {
+                coefficients,
+                seeds,
+            } => write!(
+                f,
+                "{coefficients} coefficients need {coefficients} seeds, got {seeds}"
+            ),
+            RecurrenceError::UnknownSequence(name) => write!(
+                f,
+                "unknown sequence `{name}`, expected one of {}",
+                NAMES.join(", ")
+            ),
+        }
+    }
+}
+
+impl Error for RecurrenceError {}
+
+pub const NAMES: [&str; 5] = ["fibonacci", "lucas", "tribonacci", "pell", "padovan"];
+
+impl LinearRecurrence {
+    pub fn new(coefficients: Vec<u64>, seeds: Vec<u64>) -> Result<Self, RecurrenceError> {
+        if coefficients.is_empty() {
+            return Err(RecurrenceError::Empty);
+        }
+
+        if coefficients.len() != seeds.len() {
+            return Err(RecurrenceError::LengthMismatch {
+                coefficients: coefficients.len(),
+                seeds: seeds.len(),
+            });
+        }
+
+        Ok(Self {
+            coefficients,
+            seeds,
+        })
+    }
+
+    // 0, 1, 1, 2, 3, 5, 8, ...
+    pub fn fibonacci() -> Self {
+        Self::known(&[1, 1], &[0, 1])
+    }
+
+    // 2, 1, 3, 4, 7, 11, 18, ...
+    pub fn lucas() -> Self {
+        Self::known(&[1, 1], &[2, 1])
+    }
+
+    // 0, 0, 1, 1, 2, 4, 7, 13, ...
+    pub fn tribonacci() -> Self {
+        Self::known(&[1, 1, 1], &[0, 0, 1])
+    }
+
+    // 0, 1, 2, 5, 12, 29, 70, ...
+    pub fn pell() -> Self {
+        Self::known(&[2, 1], &[0, 1])
+    }
+
+    // 1, 1, 1, 2, 2, 3, 4, 5, 7, ...
+    pub fn padovan() -> Self {
+        Self::known(&[0, 1, 1], &[1, 1, 1])
+    }
+
+    pub fn named(name: &str) -> Result<Self, RecurrenceError> {
+        match name {
+            "fibonacci" => Ok(Self::fibonacci()),
+            "lucas" => Ok(Self::lucas()),
+            "tribonacci" => Ok(Self::tribonacci()),
+            "pell" => Ok(Self::pell()),
+            "padovan" => Ok(Self::padovan()),
+            _ => Err(RecurrenceError::UnknownSequence(name.to_string())),
+        }
+    }
+
+    fn known(coefficients: &[u64], seeds: &[u64]) -> Self {
+        Self {
+            coefficients: coefficients.to_vec(),
+            seeds: seeds.to_vec(),
+        }
+    }
+
+    pub fn order(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    pub fn coefficients(&self) -> &[u64] {
+        &self.coefficients
+    }
+
+    pub fn seeds(&self) -> &[u64] {
+        &self.seeds
+    }
+
+    pub fn nth(&self, n: u64, evaluation: Evaluation) -> BigUint {
+        match evaluation {
+            Evaluation::Iterative => self.nth_iterative(n),
+            Evaluation::Matrix => self.nth_matrix(n),
+        }
+    }
+
+    // Walks the terms one by one, keeping only the last k of them.
+    pub fn nth_iterative(&self, n: u64) -> BigUint {
+        self.terms()
+            .nth(n as usize)
+            .expect("the terms never run out")
+    }
+
+    // - Raises the k x k companion matrix to the power of n - k + 1. Its first
+    //   row holds the coefficients and the rows below shift every term down
+    //   one place, so multiplying it with (a(n - 1), ..., a(n - k)) gives
+    //   (a(n), ..., a(n - k + 1)).
+    // - Only takes about log2(n) matrix multiplications.
+    pub fn nth_matrix(&self, n: u64) -> BigUint {
+        let k = self.order();
+        if n < k as u64 {
+            return BigUint::from(self.seeds[n as usize]);
+        }
+
+        let mut companion = vec![vec![BigUint::zero(); k]; k];
+        for (col, &coefficient) in self.coefficients.iter().enumerate() {
+            companion[0][col] = BigUint::from(coefficient);
+        }
+        for row in 1..k {
+            companion[row][row - 1] = BigUint::one();
+        }
+
+        let power = matrix_pow(companion, n - k as u64 + 1);
+
+        // The first row dotted with (a(k - 1), ..., a(0)).
+        power[0].iter().zip(self.seeds.iter().rev()).fold(
+            BigUint::zero(),
+            |mut sum, (entry, &seed)| {
+                sum += &(entry * &BigUint::from(seed));
+                sum
+            },
+        )
+    }
+
+    pub fn terms(&self) -> Terms<'_> {
+        Terms {
+            recurrence: self,
+            window: self.seeds.iter().copied().map(BigUint::from).collect(),
+        }
+    }
+}
+
+// Every term of a recurrence, starting from a(0).
+pub struct Terms<'a> {
+    recurrence: &'a LinearRecurrence,
+    // The next k terms, oldest first.
+    window: Vec<BigUint>,
+}
+
+impl Iterator for Terms<'_> {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<BigUint> {
+        let next = self
+            .recurrence
+            .coefficients
+            .iter()
+            .zip(self.window.iter().rev())
+            .fold(BigUint::zero(), |mut sum, (&coefficient, term)| {
+                sum += &(term * &BigUint::from(coefficient));
+                sum
+            });
+
+        self.window.push(next);
+        Some(self.window.remove(0))
+    }
+}
+
+type Matrix = Vec<Vec<BigUint>>;
+
+fn matrix_mul(x: &Matrix, y: &Matrix) -> Matrix {
+    let k = x.len();
+
+    (0..k)
+        .map(|row| {
+            (0..k)
+                .map(|col| {
+                    (0..k).fold(BigUint::zero(), |mut sum, i| {
+                        sum += &(&x[row][i] * &y[i][col]);
+                        sum
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn matrix_pow(mut base: Matrix, mut exponent: u64) -> Matrix {
+    let k = base.len();
+    let mut result: Matrix = (0..k)
+        .map(|row| {
+            (0..k)
+                .map(|col| {
+                    if row == col {
+                        BigUint::one()
+                    } else {
+                        BigUint::zero()
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = matrix_mul(&result, &base);
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = matrix_mul(&base, &base);
+        }
+    }
+
+    result
+}
+
+fn parse_list(flag: &str, value: &str) -> Result<Vec<u64>, String> {
+    value
+        .split(',')
+        .map(|item| {
+            item.trim()
+                .parse()
+                .map_err(|_| format!("`{item}` in `{flag}` is not a non-negative number"))
+        })
+        .collect()
+}
+
+// - cpc seq --coeffs 1,1 --seed 2,1 --n 50 prints the 50th term of the
+//   recurrence; --name picks one of the known sequences instead.
+// - --terms K lists the first K terms and --matrix switches evaluation.
+pub fn seq_command(args: &[String]) -> ToolResult {
+    const USAGE: &str =
+        "usage: cpc seq (--name <sequence> | --coeffs <c1,c2,..> --seed <a0,a1,..>) \
+                         (--n <n> | --terms <k>) [--matrix]";
+
+    let mut name = None;
+    let mut coefficients = None;
+    let mut seeds = None;
+    let mut n = None;
+    let mut terms = None;
+    let mut evaluation = Evaluation::default();
+
+    let mut args = args.iter().map(String::as_str);
+    while let Some(flag) = args.next() {
+        if flag == "--matrix" {
+            evaluation = Evaluation::Matrix;
+            continue;
+        }
+
+        let value = args
+            .next()
+            .ok_or_else(|| format!("`{flag}` needs a value"))?;
+        let number = || {
+            value
+                .parse::<u64>()
+                .map_err(|_| format!("`{value}` is not a non-negative number"))
+        };
+
+        match flag {
+            "--name" => name = Some(value),
+            "--coeffs" => coefficients = Some(parse_list(flag, value)?),
+            "--seed" => seeds = Some(parse_list(flag, value)?),
+            "--n" => n = Some(number()?),
+            "--terms" => terms = Some(number()?),
+            _ => return Err(USAGE.into()),
+        }
+    }
+
+    let recurrence = match (name, coefficients, seeds) {
+        (Some(name), None, None) => LinearRecurrence::named(name)?,
+        (None, Some(coefficients), Some(seeds)) => LinearRecurrence::new(coefficients, seeds)?,
+        _ => return Err(USAGE.into()),
+    };
+
+    match (n, terms) {
+        (Some(n), None) => println!("a({n}) = {}", recurrence.nth(n, evaluation)),
+        (None, Some(k)) => {
+            let terms: Vec<String> = recurrence
+                .terms()
+                .take(k as usize)
+                .map(|term| term.to_string())
+                .collect();
+            println!("{}", terms.join(", "));
+        }
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,227 @@
+use std::error::Error;
+use std::fmt;
+use std::num::IntErrorKind;
+
+use crate::ints::IntType;
+
+// - What went wrong parsing a scalar, with enough detail to tell the user
+//   how to fix it.
+// - Overflow and Underflow suggest the smallest type that would have held
+//   the value, the way the compiler does for literals that are out of range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScalarError {
+    Empty {
+        ty: &'static str,
+    },
+    InvalidDigit {
+        text: String,
+        digit: char,
+        ty: IntType,
+    },
+    Overflow {
+        text: String,
+        ty: IntType,
+        suggestion: Option<IntType>,
+    },
+    Underflow {
+        text: String,
+        ty: IntType,
+        suggestion: Option<IntType>,
+    },
+    InvalidFloat {
+        text: String,
+        ty: &'static str,
+    },
+    InvalidBool(String),
+    MultiChar {
+        text: String,
+        count: usize,
+    },
+}
+
+impl fmt::Display for ScalarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScalarError::Empty { ty } => write!(f, "nothing was entered, expected a {ty}"),
+            ScalarError::InvalidDigit { text, digit, ty } => {
+                write!(f, "`{text}` is not a valid {ty}: `{digit}` is not a digit")
+            }
+            ScalarError::Overflow {
+                text,
+                ty,
+                suggestion,
+            } => {
+                write!(f, "{text} exceeds {ty}::MAX ({})", ty.max())?;
+                write_suggestion(f, *suggestion)
+            }
+            ScalarError::Underflow {
+                text,
+                ty,
+                suggestion,
+            } => {
+                write!(f, "{text} is below {ty}::MIN ({})", ty.min())?;
+                write_suggestion(f, *suggestion)
+            }
+            ScalarError::InvalidFloat { text, ty } => write!(f, "`{text}` is not a valid {ty}"),
+            ScalarError::InvalidBool(text) => {
+                write!(f, "`{text}` is not a bool, expected true or false")
+            }
+            ScalarError::MultiChar { text, count } => write!(
+                f,
+                "`{text}` is {count} characters, a char holds exactly one"
+            ),
+        }
+    }
+}
+
+fn write_suggestion(f: &mut fmt::Formatter<'_>, suggestion: Option<IntType>) -> fmt::Result {
+    match suggestion {
+        Some(ty) => write!(f, ", try {ty}"),
+        None => f.write_str(", no integer type can hold it"),
+    }
+}
+
+impl Error for ScalarError {}
+
+// The types parse_scalar can read: every integer and float type, bool and
+// char.
+pub trait Scalar: Sized {
+    const NAME: &'static str;
+
+    fn from_text(text: &str) -> Result<Self, ScalarError>;
+}
+
+// - A drop-in for `text.parse::<T>()` that explains its errors:
+//     parse_scalar::<u8>("300") fails with "300 exceeds u8::MAX (255), try u16"
+// - Surrounding whitespace is ignored, so a line read from stdin can be
+//   passed in as it is.
+pub fn parse_scalar<T: Scalar>(text: &str) -> Result<T, ScalarError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(ScalarError::Empty { ty: T::NAME });
+    }
+
+    T::from_text(text)
+}
+
+// The std error only says what kind of problem there was; this works out
+// the rest from the text.
+fn int_error(text: &str, ty: IntType, kind: &IntErrorKind) -> ScalarError {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let magnitude = digits.parse::<u128>().ok();
+    let suggestion = || {
+        magnitude
+            .and_then(|magnitude| IntType::smallest_fitting(negative, magnitude, ty.is_signed()))
+    };
+    let text = text.to_string();
+
+    match kind {
+        IntErrorKind::PosOverflow => ScalarError::Overflow {
+            text,
+            ty,
+            suggestion: suggestion(),
+        },
+        IntErrorKind::NegOverflow => ScalarError::Underflow {
+            text,
+            ty,
+            suggestion: suggestion(),
+        },
+        // Unsigned types reject the - itself, but -5 is still a number that
+        // is too small rather than a typo.
+        _ if negative && magnitude.is_some_and(|magnitude| magnitude > 0) => {
+            ScalarError::Underflow {
+                text,
+                ty,
+                suggestion: suggestion(),
+            }
+        }
+        _ => {
+            let digit = digits
+                .chars()
+                .find(|c| !c.is_ascii_digit())
+                .or_else(|| text.chars().next())
+                .unwrap_or(' ');
+            ScalarError::InvalidDigit { text, digit, ty }
+        }
+    }
+}
+
+macro_rules! int_scalar {
+    ($($t:ty => $ty:expr),* $(,)?) => {
+        $(
+            impl Scalar for $t {
+                const NAME: &'static str = stringify!($t);
+
+                fn from_text(text: &str) -> Result<Self, ScalarError> {
+                    text.parse().map_err(|err: std::num::ParseIntError| {
+                        int_error(text, $ty, err.kind())
+                    })
+                }
+            }
+        )*
+    };
+}
+
+int_scalar! {
+    i8 => IntType::I8,
+    i16 => IntType::I16,
+    i32 => IntType::I32,
+    i64 => IntType::I64,
+    i128 => IntType::I128,
+    isize => IntType::Isize,
+    u8 => IntType::U8,
+    u16 => IntType::U16,
+    u32 => IntType::U32,
+    u64 => IntType::U64,
+    u128 => IntType::U128,
+    usize => IntType::Usize,
+}
+
+impl Scalar for f32 {
+    const NAME: &'static str = "f32";
+
+    fn from_text(text: &str) -> Result<Self, ScalarError> {
+        text.parse().map_err(|_| ScalarError::InvalidFloat {
+            text: text.to_string(),
+            ty: Self::NAME,
+        })
+    }
+}
+
+impl Scalar for f64 {
+    const NAME: &'static str = "f64";
+
+    fn from_text(text: &str) -> Result<Self, ScalarError> {
+        text.parse().map_err(|_| ScalarError::InvalidFloat {
+            text: text.to_string(),
+            ty: Self::NAME,
+        })
+    }
+}
+
+impl Scalar for bool {
+    const NAME: &'static str = "bool";
+
+    fn from_text(text: &str) -> Result<Self, ScalarError> {
+        text.parse()
+            .map_err(|_| ScalarError::InvalidBool(text.to_string()))
+    }
+}
+
+impl Scalar for char {
+    const NAME: &'static str = "char";
+
+    fn from_text(text: &str) -> Result<Self, ScalarError> {
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(ScalarError::MultiChar {
+                text: text.to_string(),
+                count: text.chars().count(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::cli::ToolResult;
+use crate::rational::{ParseRationalError, Rational};
+
+// - The chapter 3 exercise, converting between Fahrenheit and Celsius, plus
+//   Kelvin and Rankine.
+// - Values are exact fractions, so converting to another scale and back
+//   always gives the starting value: 100 °F is 340/9 °C and not 37.77..8.
+// - Rows of a table are only rounded when they're printed.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scale {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    Rankine,
+}
+
+impl Scale {
+    pub const ALL: [Scale; 4] = [
+        Scale::Celsius,
+        Scale::Fahrenheit,
+        Scale::Kelvin,
+        Scale::Rankine,
+    ];
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Scale::Celsius => "°C",
+            Scale::Fahrenheit => "°F",
+            Scale::Kelvin => "K",
+            Scale::Rankine => "°R",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Scale::Celsius => "Celsius",
+            Scale::Fahrenheit => "Fahrenheit",
+            Scale::Kelvin => "Kelvin",
+            Scale::Rankine => "Rankine",
+        }
+    }
+
+    // - kelvin = (value + offset) × factor, so the offset is absolute zero
+    //   measured on this scale, negated.
+    // - The temperature units of `cpc convert` are built from these too.
+    pub const fn offset(self) -> Rational {
+        match self {
+            Scale::Celsius => Rational::new(27_315, 100),
+            Scale::Fahrenheit => Rational::new(45_967, 100),
+            Scale::Kelvin | Scale::Rankine => Rational::ZERO,
+        }
+    }
+
+    pub const fn factor(self) -> Rational {
+        match self {
+            Scale::Celsius | Scale::Kelvin => Rational::ONE,
+            Scale::Fahrenheit | Scale::Rankine => Rational::new(5, 9),
+        }
+    }
+
+    // Where this scale's value is kept in a Temperature, the order of ALL.
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for Scale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+impl FromStr for Scale {
+    type Err = TemperatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().trim_start_matches('°').to_lowercase();
+        Scale::ALL
+            .into_iter()
+            .find(|scale| {
+                name == scale.name().to_lowercase() || name == scale.name()[..1].to_lowercase()
+            })
+            .ok_or_else(|| TemperatureError::UnknownScale(s.trim().to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemperatureError {
+    UnknownScale(String),
+    InvalidNumber(ParseRationalError),
+    BelowAbsoluteZero { value: Rational, scale: Scale },
+    OutOfRange { value: Rational, scale: Scale },
+    InvalidStep(Rational),
+    Reversed { from: Rational, to: Rational },
+    TooManyRows(i128),
+}
+
+// More rows than anyone would read, and a sign of a typo in --step.
+const MAX_ROWS: i128 = 1_000;
+
+impl fmt::Display for TemperatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemperatureError::UnknownScale(name) => write!(
+                f,
+                "unknown temperature scale `{name}`, expected C, F, K or R"
+            ),
+            TemperatureError::InvalidNumber(err) => err.fmt(f),
+            TemperatureError::BelowAbsoluteZero { value, scale } => write!(
+                f,
+                "{} {scale} is below absolute zero, which is {} on that scale",
+                value.to_decimal(2),
+                Temperature::absolute_zero(*scale)
+            ),
+            TemperatureError::OutOfRange { value, scale } => write!(
+                f,
+                "{value} {scale} is too far out to be converted exactly"
+            ),
+            TemperatureError::InvalidStep(step) => {
+                write!(f, "the step has to be above zero, found {}", step.to_decimal(6))
+            }
+            TemperatureError::Reversed { from, to } => write!(
+                f,
+                "--from {} is above --to {}",
+                from.to_decimal(6),
+                to.to_decimal(6)
+            ),
+            TemperatureError::TooManyRows(rows) => write!(
+                f,
+                "the table would have {rows} rows, at most {MAX_ROWS} are allowed; use a larger --step"
+            ),
+        }
+    }
+}
+
+impl Error for TemperatureError {}
+
+impl From<ParseRationalError> for TemperatureError {
+    fn from(err: ParseRationalError) -> Self {
+        TemperatureError::InvalidNumber(err)
+    }
+}
+
+// - The value on every scale is worked out once, when the temperature is
+//   made, with checked arithmetic; a value so large or so finely divided
+//   that a conversion would overflow is refused there.
+// - After that, moving to another scale can't fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Temperature {
+    scale: Scale,
+    values: [Rational; 4],
+}
+
+impl Temperature {
+    pub fn new(value: Rational, scale: Scale) -> Result<Temperature, TemperatureError> {
+        let out_of_range = || TemperatureError::OutOfRange { value, scale };
+        let kelvin = value
+            .checked_add(scale.offset())
+            .and_then(|sum| sum.checked_mul(scale.factor()))
+            .ok_or_else(out_of_range)?;
+        if kelvin.is_negative() {
+            return Err(TemperatureError::BelowAbsoluteZero { value, scale });
+        }
+
+        let mut values = [Rational::ZERO; 4];
+        for other in Scale::ALL {
+            values[other.index()] = if other == scale {
+                value
+            } else {
+                kelvin
+                    .checked_div(other.factor())
+                    .and_then(|value| value.checked_sub(other.offset()))
+                    .ok_or_else(out_of_range)?
+            };
+        }
+
+        Ok(Temperature { scale, values })
+    }
+
+    pub fn absolute_zero(scale: Scale) -> Temperature {
+        Temperature::new(-scale.offset(), scale).expect("absolute zero is on every scale")
+    }
+
+    pub fn value(&self) -> Rational {
+        self.values[self.scale.index()]
+    }
+
+    pub fn scale(&self) -> Scale {
+        self.scale
+    }
+
+    pub fn kelvin(&self) -> Rational {
+        self.values[Scale::Kelvin.index()]
+    }
+
+    // The same temperature on another scale, exactly.
+    pub fn to(&self, scale: Scale) -> Temperature {
+        Temperature { scale, ..*self }
+    }
+}
+
+// Rounded to two places, which is as far as a table needs to go; to_decimal
+// gives more when it matters.
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.value().to_decimal(2), self.scale)
+    }
+}
+
+// - The temperatures from `from` to `to` in steps of `step`, all on
+//   `scale`. `to` is only included when a step lands on it.
+// - Every row goes through Temperature::new, so it's checked against
+//   absolute zero and for values too large to convert.
+pub fn table(
+    from: Rational,
+    to: Rational,
+    step: Rational,
+    scale: Scale,
+) -> Result<Vec<Temperature>, TemperatureError> {
+    if step <= Rational::ZERO {
+        return Err(TemperatureError::InvalidStep(step));
+    }
+    if from > to {
+        return Err(TemperatureError::Reversed { from, to });
+    }
+
+    let out_of_range = |value| TemperatureError::OutOfRange { value, scale };
+    let span = to
+        .checked_sub(from)
+        .and_then(|span| span.checked_div(step))
+        .ok_or(out_of_range(to))?;
+    let rows = span.numer().div_euclid(span.denom()) + 1;
+    if rows > MAX_ROWS {
+        return Err(TemperatureError::TooManyRows(rows));
+    }
+
+    (0..rows)
+        .map(|i| {
+            let value = step
+                .checked_mul(Rational::integer(i))
+                .and_then(|offset| from.checked_add(offset))
+                .ok_or(out_of_range(to))?;
+            Temperature::new(value, scale)
+        })
+        .collect()
+}
+
+fn print_table(rows: &[Temperature]) {
+    let header: Vec<String> = Scale::ALL
+        .iter()
+        .map(|scale| format!("{:>10}", scale.symbol()))
+        .collect();
+    println!("{}", header.concat());
+
+    for row in rows {
+        let cells: Vec<String> = Scale::ALL
+            .iter()
+            .map(|&scale| format!("{:>10}", row.to(scale).value().to_decimal(2)))
+            .collect();
+        println!("{}", cells.concat());
+    }
+}
+
+// A value that needed rounding gets a ≈, 100 °F is ≈ 37.777778 °C.
+fn describe(temperature: Temperature) -> String {
+    let value = temperature.value();
+    let rounded = value.to_decimal(6);
+    let exact = rounded.parse::<Rational>() == Ok(value);
+    let approx = if exact { "" } else { "≈ " };
+    format!("{approx}{rounded} {}", temperature.scale())
+}
+
+// - cpc temp 98.6 F shows a temperature on all four scales.
+// - cpc temp --from -40 --to 100 --step 10 --unit C prints a table with a
+//   row every 10 °C.
+pub fn temp_command(args: &[String]) -> ToolResult {
+    const USAGE: &str =
+        "usage: cpc temp <value> <unit> | --from <value> --to <value> [--step <value>] [--unit C|F|K|R]";
+
+    let mut from = None;
+    let mut to = None;
+    let mut step = Rational::integer(10);
+    let mut scale = None;
+    let mut positional = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().ok_or(format!("`{flag}` needs a value"));
+        match arg.as_str() {
+            "--from" => from = Some(value("--from")?.parse::<Rational>()?),
+            "--to" => to = Some(value("--to")?.parse::<Rational>()?),
+            "--step" => step = value("--step")?.parse()?,
+            "--unit" => scale = Some(value("--unit")?.parse::<Scale>()?),
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    match (from, to, positional.as_slice()) {
+        (Some(from), Some(to), []) => {
+            let rows = table(from, to, step, scale.unwrap_or(Scale::Celsius))?;
+            print_table(&rows);
+        }
+        (None, None, [value, unit]) if scale.is_none() => {
+            let temperature = Temperature::new(value.parse()?, unit.parse()?)?;
+            println!("{}", describe(temperature));
+            for scale in Scale::ALL {
+                if scale != temperature.scale() {
+                    println!("  {}", describe(temperature.to(scale)));
+                }
+            }
+        }
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(())
+}
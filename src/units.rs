@@ -0,0 +1,506 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::cli::ToolResult;
+use crate::rational::Rational;
+use crate::temperature::Scale;
+use Dimension::{DataSize, Length, Mass, Time};
+
+// - Quantities with a unit, and conversions between units that measure the
+//   same thing: `cpc convert 5m ft`.
+// - Every unit is an exact fraction of its dimension's base unit (metre,
+//   gram, second, kelvin and byte), so 1 ft is exactly 12 in. The ratio
+//   between two units and the offset are worked out exactly, but the value
+//   itself is an f64 and is rounded as it's scaled by prefixes and the ratio.
+// - Temperatures also have an offset, their zero isn't absolute zero.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Length,
+    Mass,
+    Time,
+    Temperature,
+    DataSize,
+}
+
+impl Dimension {
+    pub fn name(self) -> &'static str {
+        match self {
+            Dimension::Length => "length",
+            Dimension::Mass => "mass",
+            Dimension::Time => "time",
+            Dimension::Temperature => "temperature",
+            Dimension::DataSize => "data size",
+        }
+    }
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+const fn ratio(num: i128, den: i128) -> Rational {
+    Rational::new(num, den)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Power {
+    Ten(i32),
+    Two(i32),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Prefix {
+    pub symbol: &'static str,
+    pub name: &'static str,
+    power: Power,
+}
+
+const fn prefix(symbol: &'static str, name: &'static str, power: Power) -> Prefix {
+    Prefix {
+        symbol,
+        name,
+        power,
+    }
+}
+
+// Longer symbols first, so da is tried before d.
+static PREFIXES: [Prefix; 28] = [
+    prefix("da", "deca", Power::Ten(1)),
+    prefix("Ki", "kibi", Power::Two(10)),
+    prefix("Mi", "mebi", Power::Two(20)),
+    prefix("Gi", "gibi", Power::Two(30)),
+    prefix("Ti", "tebi", Power::Two(40)),
+    prefix("Pi", "pebi", Power::Two(50)),
+    prefix("Ei", "exbi", Power::Two(60)),
+    prefix("Q", "quetta", Power::Ten(30)),
+    prefix("R", "ronna", Power::Ten(27)),
+    prefix("Y", "yotta", Power::Ten(24)),
+    prefix("Z", "zetta", Power::Ten(21)),
+    prefix("E", "exa", Power::Ten(18)),
+    prefix("P", "peta", Power::Ten(15)),
+    prefix("T", "tera", Power::Ten(12)),
+    prefix("G", "giga", Power::Ten(9)),
+    prefix("M", "mega", Power::Ten(6)),
+    prefix("k", "kilo", Power::Ten(3)),
+    prefix("h", "hecto", Power::Ten(2)),
+    prefix("d", "deci", Power::Ten(-1)),
+    prefix("c", "centi", Power::Ten(-2)),
+    prefix("m", "milli", Power::Ten(-3)),
+    prefix("µ", "micro", Power::Ten(-6)),
+    prefix("u", "micro", Power::Ten(-6)),
+    prefix("n", "nano", Power::Ten(-9)),
+    prefix("p", "pico", Power::Ten(-12)),
+    prefix("f", "femto", Power::Ten(-15)),
+    prefix("a", "atto", Power::Ten(-18)),
+    prefix("z", "zepto", Power::Ten(-21)),
+];
+
+// Multiplies by the prefix, or divides when `inverse` is set. Powers of ten
+// are applied by dividing for negative exponents, since 10^-3 isn't exact
+// as an f64 but 1000 is.
+fn apply(value: f64, power: Power, inverse: bool) -> f64 {
+    let (base, exponent) = match power {
+        Power::Ten(exponent) => (10f64, exponent),
+        Power::Two(exponent) => (2f64, exponent),
+    };
+    let exponent = if inverse { -exponent } else { exponent };
+    let scale = base.powi(exponent.abs());
+    if exponent >= 0 {
+        value * scale
+    } else {
+        value / scale
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Prefixes {
+    None,
+    Si,
+    // Data sizes take binary prefixes too: KiB, MiB.
+    SiAndBinary,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Unit {
+    pub symbol: &'static str,
+    pub name: &'static str,
+    aliases: &'static [&'static str],
+    pub dimension: Dimension,
+    // value in the base unit = (value + offset) × factor
+    factor: Rational,
+    offset: Rational,
+    prefixes: Prefixes,
+}
+
+const fn unit(
+    symbol: &'static str,
+    name: &'static str,
+    aliases: &'static [&'static str],
+    dimension: Dimension,
+    factor: Rational,
+    prefixes: Prefixes,
+) -> Unit {
+    Unit {
+        symbol,
+        name,
+        aliases,
+        dimension,
+        factor,
+        offset: ratio(0, 1),
+        prefixes,
+    }
+}
+
+// The factor and offset come from `Scale`, which cpc temp uses too.
+const fn temperature(
+    symbol: &'static str,
+    name: &'static str,
+    aliases: &'static [&'static str],
+    scale: Scale,
+    prefixes: Prefixes,
+) -> Unit {
+    Unit {
+        symbol,
+        name,
+        aliases,
+        dimension: Dimension::Temperature,
+        factor: scale.factor(),
+        offset: scale.offset(),
+        prefixes,
+    }
+}
+
+#[rustfmt::skip]
+static UNITS: [Unit; 24] = [
+    unit("m", "metre", &["meter", "metres", "meters"], Length, ratio(1, 1), Prefixes::Si),
+    unit("in", "inch", &["inches", "\""], Length, ratio(254, 10_000), Prefixes::None),
+    unit("ft", "foot", &["feet", "'"], Length, ratio(3_048, 10_000), Prefixes::None),
+    unit("yd", "yard", &["yards"], Length, ratio(9_144, 10_000), Prefixes::None),
+    unit("mi", "mile", &["miles"], Length, ratio(1_609_344, 1_000), Prefixes::None),
+    unit("nmi", "nautical mile", &[], Length, ratio(1_852, 1), Prefixes::None),
+    unit("g", "gram", &["grams"], Mass, ratio(1, 1), Prefixes::Si),
+    unit("t", "tonne", &["tonnes"], Mass, ratio(1_000_000, 1), Prefixes::None),
+    unit("lb", "pound", &["lbs", "pounds"], Mass, ratio(45_359_237, 100_000), Prefixes::None),
+    unit("oz", "ounce", &["ounces"], Mass, ratio(28_349_523_125, 1_000_000_000), Prefixes::None),
+    unit("st", "stone", &["stones"], Mass, ratio(635_029_318, 100_000), Prefixes::None),
+    unit("s", "second", &["sec", "seconds"], Time, ratio(1, 1), Prefixes::Si),
+    unit("min", "minute", &["minutes"], Time, ratio(60, 1), Prefixes::None),
+    unit("h", "hour", &["hr", "hours"], Time, ratio(3_600, 1), Prefixes::None),
+    unit("d", "day", &["days"], Time, ratio(86_400, 1), Prefixes::None),
+    unit("wk", "week", &["weeks"], Time, ratio(604_800, 1), Prefixes::None),
+    // The Julian year of 365.25 days that astronomers use.
+    unit("yr", "year", &["years"], Time, ratio(31_557_600, 1), Prefixes::None),
+    temperature("K", "kelvin", &["kelvins"], Scale::Kelvin, Prefixes::Si),
+    temperature("°C", "degree Celsius", &["C", "degC", "celsius"], Scale::Celsius, Prefixes::None),
+    temperature("°F", "degree Fahrenheit", &["F", "degF", "fahrenheit"], Scale::Fahrenheit, Prefixes::None),
+    temperature("°R", "degree Rankine", &["R", "degR", "rankine"], Scale::Rankine, Prefixes::None),
+    unit("B", "byte", &["bytes"], DataSize, ratio(1, 1), Prefixes::SiAndBinary),
+    unit("bit", "bit", &["b", "bits"], DataSize, ratio(1, 8), Prefixes::SiAndBinary),
+    unit("nibble", "nibble", &["nibbles"], DataSize, ratio(1, 2), Prefixes::None),
+];
+
+impl Unit {
+    fn named(name: &str) -> Option<&'static Unit> {
+        UNITS
+            .iter()
+            .find(|unit| unit.symbol == name || unit.name == name || unit.aliases.contains(&name))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnitError {
+    InvalidNumber(String),
+    UnknownUnit(String),
+    NoPrefixes {
+        prefix: &'static str,
+        unit: &'static str,
+    },
+    BinaryPrefix {
+        prefix: &'static str,
+        unit: &'static str,
+    },
+    Incompatible {
+        from: PrefixedUnit,
+        to: PrefixedUnit,
+    },
+    BelowAbsoluteZero(String),
+}
+
+impl fmt::Display for UnitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitError::InvalidNumber(text) => write!(f, "`{text}` is not a number"),
+            UnitError::UnknownUnit(text) => {
+                let symbols: Vec<&str> = UNITS.iter().map(|unit| unit.symbol).collect();
+                write!(
+                    f,
+                    "unknown unit `{text}`, expected one of {} with an optional prefix like k or Mi",
+                    symbols.join(", ")
+                )
+            }
+            UnitError::NoPrefixes { prefix, unit } => {
+                write!(f, "`{unit}` doesn't take prefixes, so `{prefix}{unit}` isn't a unit")
+            }
+            UnitError::BinaryPrefix { prefix, unit } => write!(
+                f,
+                "binary prefixes like `{prefix}` are only for data sizes, `{prefix}{unit}` isn't a unit"
+            ),
+            UnitError::Incompatible { from, to } => write!(
+                f,
+                "cannot convert `{from}` to `{to}`: {from} measures {} but {to} measures {}",
+                from.unit.dimension, to.unit.dimension
+            ),
+            UnitError::BelowAbsoluteZero(quantity) => {
+                write!(f, "{quantity} is below absolute zero")
+            }
+        }
+    }
+}
+
+impl Error for UnitError {}
+
+// A unit with an optional prefix, the km in 5 km.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrefixedUnit {
+    pub prefix: Option<&'static Prefix>,
+    pub unit: &'static Unit,
+}
+
+impl PrefixedUnit {
+    pub fn dimension(&self) -> Dimension {
+        self.unit.dimension
+    }
+}
+
+impl FromStr for PrefixedUnit {
+    type Err = UnitError;
+
+    // - Whole symbols and names win, so min is a minute and not a milli-inch.
+    // - Otherwise the longest prefix that leaves a known unit is used.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        if let Some(unit) = Unit::named(text) {
+            return Ok(PrefixedUnit { prefix: None, unit });
+        }
+
+        for prefix in &PREFIXES {
+            let Some(unit) = text.strip_prefix(prefix.symbol).and_then(Unit::named) else {
+                continue;
+            };
+
+            return match (unit.prefixes, prefix.power) {
+                (Prefixes::None, _) => Err(UnitError::NoPrefixes {
+                    prefix: prefix.symbol,
+                    unit: unit.symbol,
+                }),
+                (Prefixes::Si, Power::Two(_)) => Err(UnitError::BinaryPrefix {
+                    prefix: prefix.symbol,
+                    unit: unit.symbol,
+                }),
+                _ => Ok(PrefixedUnit {
+                    prefix: Some(prefix),
+                    unit,
+                }),
+            };
+        }
+
+        Err(UnitError::UnknownUnit(text.to_string()))
+    }
+}
+
+impl fmt::Display for PrefixedUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(prefix) = self.prefix {
+            f.write_str(prefix.symbol)?;
+        }
+        f.write_str(self.unit.symbol)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity {
+    pub value: f64,
+    pub unit: PrefixedUnit,
+}
+
+impl Quantity {
+    pub fn new(value: f64, unit: &str) -> Result<Quantity, UnitError> {
+        let quantity = Quantity {
+            value,
+            unit: unit.parse()?,
+        };
+
+        // - Only temperatures have an offset.
+        // - Kelvin is the one temperature that takes prefixes, and its offset
+        //   is zero, so a prefix can't move a value across absolute zero.
+        let unit = quantity.unit.unit;
+        if unit.dimension == Dimension::Temperature && value + unit.offset.to_f64() < 0.0 {
+            return Err(UnitError::BelowAbsoluteZero(quantity.to_string()));
+        }
+
+        Ok(quantity)
+    }
+
+    // - The same quantity in another unit of the same dimension.
+    // - With factors f and offsets o: (value + o_from) × f_from / f_to - o_to,
+    //   where the fraction and the constant are worked out exactly first.
+    // - That's in the units without their prefixes, so the value is scaled
+    //   by the source prefix before it and by the target prefix after it.
+    pub fn convert_to(&self, target: PrefixedUnit) -> Result<Quantity, UnitError> {
+        let from = self.unit;
+        if from.dimension() != target.dimension() {
+            return Err(UnitError::Incompatible { from, to: target });
+        }
+
+        let scale = from.unit.factor / target.unit.factor;
+        let constant = from.unit.offset * scale - target.unit.offset;
+
+        let mut value = self.value;
+        if let Some(prefix) = from.prefix {
+            value = apply(value, prefix.power, false);
+        }
+        value = value * scale.numer() as f64 / scale.denom() as f64 + constant.to_f64();
+        if let Some(prefix) = target.prefix {
+            value = apply(value, prefix.power, true);
+        }
+
+        Ok(Quantity {
+            value,
+            unit: target,
+        })
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.value, self.unit)
+    }
+}
+
+// Splits 5m into 5 and m, and -40°C into -40 and °C.
+fn split_number(text: &str) -> (&str, &str) {
+    let bytes = text.as_bytes();
+    let mut end = 0;
+
+    if matches!(bytes.first(), Some(b'-' | b'+')) {
+        end = 1;
+    }
+    while end < bytes.len() {
+        match bytes[end] {
+            b'0'..=b'9' | b'.' | b'_' => end += 1,
+            // An exponent only when digits follow, 5e is 5 of some unit e.
+            b'e' | b'E' => {
+                let digits = match bytes.get(end + 1) {
+                    Some(b'-' | b'+') => end + 2,
+                    _ => end + 1,
+                };
+                if !bytes.get(digits).is_some_and(u8::is_ascii_digit) {
+                    break;
+                }
+                end = digits;
+            }
+            _ => break,
+        }
+    }
+
+    (&text[..end], text[end..].trim())
+}
+
+// A prefix as an exact fraction, None when it doesn't fit in one.
+fn prefix_ratio(prefix: Option<&Prefix>) -> Option<Rational> {
+    let (base, exponent): (i128, i32) = match prefix.map(|prefix| prefix.power) {
+        None => return Some(Rational::ONE),
+        Some(Power::Ten(exponent)) => (10, exponent),
+        Some(Power::Two(exponent)) => (2, exponent),
+    };
+    let magnitude = base.checked_pow(exponent.unsigned_abs())?;
+
+    Some(if exponent >= 0 {
+        Rational::integer(magnitude)
+    } else {
+        Rational::new(1, magnitude)
+    })
+}
+
+// - The scale and constant of `target = from × scale + constant`, prefixes
+//   included: 1 mK is 1/1000 K.
+// - None when they don't fit in a Rational, from zK to QK.
+fn temperature_formula(from: PrefixedUnit, target: PrefixedUnit) -> Option<(Rational, Rational)> {
+    let unit_scale = from.unit.factor.checked_div(target.unit.factor)?;
+    let target_prefix = prefix_ratio(target.prefix)?;
+
+    let scale = prefix_ratio(from.prefix)?
+        .checked_mul(unit_scale)?
+        .checked_div(target_prefix)?;
+    let constant = from
+        .unit
+        .offset
+        .checked_mul(unit_scale)?
+        .checked_sub(target.unit.offset)?
+        .checked_div(target_prefix)?;
+
+    Some((scale, constant))
+}
+
+// - cpc convert 5m ft, cpc convert 5 km to mi and cpc convert -40 °C °F.
+// - Prints the converted quantity and how the two units relate.
+pub fn convert_command(args: &[String]) -> ToolResult {
+    const USAGE: &str = "usage: cpc convert <value><unit> [to] <unit>";
+
+    let mut args = args.iter().map(String::as_str);
+    let (number, mut unit) = split_number(args.next().ok_or(USAGE)?);
+    if unit.is_empty() {
+        unit = args.next().ok_or(USAGE)?;
+    }
+    let target = match args.next().ok_or(USAGE)? {
+        "to" => args.next().ok_or(USAGE)?,
+        target => target,
+    };
+    if args.next().is_some() {
+        return Err(USAGE.into());
+    }
+
+    let value: f64 = number
+        .replace('_', "")
+        .parse()
+        .map_err(|_| UnitError::InvalidNumber(number.to_string()))?;
+    let quantity = Quantity::new(value, unit)?;
+    let target: PrefixedUnit = target.parse()?;
+    let converted = quantity.convert_to(target)?;
+
+    println!("{quantity} = {converted}");
+
+    let from = quantity.unit;
+    if from.dimension() == Dimension::Temperature {
+        // An offset means there's no single factor, so show the formula,
+        // unless the prefixes are too far apart for an exact one.
+        if let Some((scale, constant)) = temperature_formula(from, target) {
+            let constant = constant.to_f64();
+            let mut formula = format!("  {target} = {from}");
+            if scale != Rational::ONE {
+                formula.push_str(&format!(" × {scale}"));
+            }
+            if constant != 0.0 {
+                let sign = if constant < 0.0 { '-' } else { '+' };
+                formula.push_str(&format!(" {sign} {}", constant.abs()));
+            }
+            println!("{formula}");
+        }
+    } else {
+        let one = Quantity {
+            value: 1.0,
+            unit: from,
+        };
+        println!(
+            "  {}: {one} = {}",
+            from.dimension(),
+            one.convert_to(target)?
+        );
+    }
+
+    Ok(())
+}
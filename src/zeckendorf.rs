@@ -0,0 +1,256 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use crate::cli::ToolResult;
+use crate::fibonnacci::Fibonacci;
+use crate::scalar::parse_scalar;
+
+// - Zeckendorf's theorem: every positive integer can be written in exactly
+//   one way as a sum of Fibonnacci numbers that are not next to each other
+//   in the sequence, e.g. 100 = 89 + 8 + 3.
+// - Taking the largest Fibonnacci number that still fits at every step
+//   always finds that sum.
+// - The terms are returned as (standard index, value), largest first, and
+//   never use F(0) or F(1), so 1 is F(2).
+pub fn zeckendorf(n: u64) -> Vec<(u64, u64)> {
+    let numbers: Vec<u64> = Fibonacci::new().take_until(n).collect();
+    let mut rest = n;
+    let mut terms = Vec::new();
+
+    for (index, &number) in numbers.iter().enumerate().skip(2).rev() {
+        if number <= rest {
+            terms.push((index as u64, number));
+            rest -= number;
+        }
+
+        if rest == 0 {
+            break;
+        }
+    }
+
+    terms
+}
+
+// - The Fibonnacci code of a positive integer writes down its Zeckendorf sum
+//   as bits, lowest term first: bit i is set when F(i + 2) is used. Because
+//   the sum never uses two neighbouring numbers, "11" never shows up inside
+//   a code, so a 1 is added at the end to mark where it stops:
+//     1 = F(2)        -> 1 1
+//     4 = F(4) + F(2) -> 1 0 1 1
+//    11 = F(6) + F(4) -> 0 0 1 0 1 1
+// - Codes are packed one after the other, most significant bit of each byte
+//   first. The last byte is padded with zeros, which can't be mistaken for
+//   another code because every code ends in "11".
+pub fn encode(values: &[u64]) -> Result<Vec<u8>, FibCodeError> {
+    let mut writer = BitWriter::default();
+
+    for &value in values {
+        for bit in codeword(value)? {
+            writer.push(bit);
+        }
+    }
+
+    Ok(writer.finish())
+}
+
+pub fn decode(bytes: &[u8]) -> Result<Vec<u64>, FibCodeError> {
+    decode_bits(
+        bytes
+            .iter()
+            .flat_map(|&byte| (0..8).rev().map(move |i| byte >> i & 1 == 1)),
+    )
+}
+
+// The bits of a single value's code, terminating 1 included.
+pub fn codeword(value: u64) -> Result<Vec<bool>, FibCodeError> {
+    if value == 0 {
+        return Err(FibCodeError::Zero);
+    }
+
+    let terms = zeckendorf(value);
+    let highest = terms[0].0;
+    let mut bits = vec![false; highest as usize - 1];
+
+    for (index, _) in terms {
+        bits[index as usize - 2] = true;
+    }
+    bits.push(true);
+
+    Ok(bits)
+}
+
+// Decodes a stream of single bits, so the text form can share the logic.
+pub fn decode_bits(bits: impl IntoIterator<Item = bool>) -> Result<Vec<u64>, FibCodeError> {
+    let numbers: Vec<u64> = Fibonacci::new().collect();
+    let mut values = Vec::new();
+
+    let mut value: u64 = 0;
+    let mut position = 0;
+    let mut previous = false;
+
+    for bit in bits {
+        if bit && previous {
+            values.push(value);
+            value = 0;
+            position = 0;
+            previous = false;
+            continue;
+        }
+
+        if bit {
+            value = numbers
+                .get(position + 2)
+                .and_then(|&number| value.checked_add(number))
+                .ok_or(FibCodeError::Overflow)?;
+        }
+
+        previous = bit;
+        position += 1;
+    }
+
+    // Whatever is left is either zero padding or a code that was cut off.
+    if value != 0 {
+        return Err(FibCodeError::Truncated);
+    }
+
+    Ok(values)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FibCodeError {
+    Zero,
+    Overflow,
+    Truncated,
+    InvalidBit(char),
+}
+
+impl fmt::Display for FibCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FibCodeError::Zero => f.write_str("only positive integers have a Fibonnacci code"),
+            FibCodeError::Overflow => f.write_str("a code in the input is too large for a u64"),
+            FibCodeError::Truncated => f.write_str("the input ends in the middle of a code"),
+            FibCodeError::InvalidBit(c) => write!(f, "`{c}` is not a bit, expected 0 or 1"),
+        }
+    }
+}
+
+impl Error for FibCodeError {}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    used: u32,
+}
+
+impl BitWriter {
+    fn push(&mut self, bit: bool) {
+        if self.used.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+
+        if bit {
+            let last = self.bytes.len() - 1;
+            self.bytes[last] |= 0x80 >> (self.used % 8);
+        }
+
+        self.used += 1;
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+pub fn zeckendorf_command(args: &[String]) -> ToolResult {
+    let [n] = args else {
+        return Err("usage: cpc zeckendorf <n>".into());
+    };
+
+    let n: u64 = n
+        .parse()
+        .map_err(|_| format!("`{n}` is not a non-negative number"))?;
+
+    if n == 0 {
+        println!("0 is the empty sum");
+        return Ok(());
+    }
+
+    let terms = zeckendorf(n);
+    let values: Vec<String> = terms.iter().map(|(_, value)| value.to_string()).collect();
+    let indices: Vec<String> = terms
+        .iter()
+        .map(|(index, _)| format!("F({index})"))
+        .collect();
+
+    println!("{n} = {}", values.join(" + "));
+    println!("{n} = {}", indices.join(" + "));
+    Ok(())
+}
+
+// - encode reads whitespace separated positive integers from stdin and
+//   writes the packed codes to stdout; decode does the reverse.
+// - With --text the codes are written as 0s and 1s instead of raw bytes,
+//   one code per word, and decode accepts that form back.
+pub fn fibcode_command(args: &[String]) -> ToolResult {
+    let (direction, text) = match args {
+        [direction] => (direction.as_str(), false),
+        [direction, flag] if flag == "--text" => (direction.as_str(), true),
+        _ => return Err("usage: cpc fibcode encode|decode [--text]".into()),
+    };
+
+    let mut input = Vec::new();
+    io::stdin().read_to_end(&mut input)?;
+    let mut stdout = io::stdout().lock();
+
+    match direction {
+        "encode" => {
+            let values = String::from_utf8(input)?
+                .split_whitespace()
+                .map(parse_scalar::<u64>)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if text {
+                let words = values
+                    .iter()
+                    .map(|&value| {
+                        codeword(value).map(|bits| {
+                            bits.iter()
+                                .map(|&bit| if bit { '1' } else { '0' })
+                                .collect()
+                        })
+                    })
+                    .collect::<Result<Vec<String>, _>>()?;
+                writeln!(stdout, "{}", words.join(" "))?;
+            } else {
+                stdout.write_all(&encode(&values)?)?;
+            }
+        }
+        "decode" => {
+            let values = if text {
+                let bits = String::from_utf8(input)?
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .map(|c| match c {
+                        '0' => Ok(false),
+                        '1' => Ok(true),
+                        c => Err(FibCodeError::InvalidBit(c)),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                decode_bits(bits)?
+            } else {
+                decode(&input)?
+            };
+
+            for value in values {
+                writeln!(stdout, "{value}")?;
+            }
+        }
+        other => {
+            return Err(format!("unknown direction `{other}`, expected encode or decode").into())
+        }
+    }
+
+    Ok(())
+}
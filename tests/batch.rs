@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use cpc::batch::{evaluate, parse_queries, write_results, OutputFormat};
+use cpc::fibonnacci::{fibonnacci_big, FibAlgorithm, FibIndexing};
+
+fn written(queries: &[u64], format: OutputFormat) -> String {
+    let results = evaluate(queries, FibAlgorithm::default());
+    let mut out = Vec::new();
+    write_results(&mut out, queries, &results, format).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn batches_keep_the_input_order() {
+    // Large, repeated and small queries mixed, so some run on other threads.
+    let queries = [20_000, 5, 15_000, 0, 20_000, 100, 12_345, 1];
+    let results = evaluate(&queries, FibAlgorithm::default());
+
+    assert_eq!(results.len(), queries.len());
+    for (&n, value) in queries.iter().zip(&results) {
+        assert_eq!(**value, *fibonnacci_big(n).to_string(), "n = {n}");
+    }
+    assert!(Arc::ptr_eq(&results[0], &results[4]));
+}
+
+#[test]
+fn queries_are_one_per_line() {
+    assert_eq!(
+        parse_queries("10\n\n  3 \n0\n", FibIndexing::Standard).unwrap(),
+        [10, 3, 0]
+    );
+    assert_eq!(
+        parse_queries("1\n2\n", FibIndexing::Classic).unwrap(),
+        [1, 2]
+    );
+
+    let error = parse_queries("1\n\nten\n", FibIndexing::Standard).unwrap_err();
+    assert!(error.to_string().contains("line 3"), "{error}");
+    let error = parse_queries("4\n0\n", FibIndexing::Classic).unwrap_err();
+    assert!(error.to_string().contains("line 2"), "{error}");
+    assert!(parse_queries("-1\n", FibIndexing::Standard).is_err());
+}
+
+#[test]
+fn every_format_lists_the_results_in_order() {
+    assert_eq!(
+        written(&[10, 1, 100], OutputFormat::Text),
+        "55\n1\n354224848179261915075\n"
+    );
+    assert_eq!(
+        written(&[10, 1], OutputFormat::Csv),
+        "n,value\n10,55\n1,1\n"
+    );
+    assert_eq!(
+        written(&[10, 100], OutputFormat::Json),
+        "[\n  {\"n\": 10, \"value\": \"55\"},\n  \
+         {\"n\": 100, \"value\": \"354224848179261915075\"}\n]\n"
+    );
+    assert_eq!(written(&[], OutputFormat::Json), "[\n]\n");
+
+    assert_eq!("csv".parse(), Ok(OutputFormat::Csv));
+    assert!("xml".parse::<OutputFormat>().is_err());
+}
@@ -0,0 +1,49 @@
+use cpc::bits::{bits_command, infer_type, Type};
+use cpc::floats::FloatType;
+use cpc::ints::IntType;
+
+fn args(value: &str) -> Vec<String> {
+    vec![value.to_string()]
+}
+
+#[test]
+fn integer_suffixes_are_never_exponents() {
+    assert_eq!(infer_type("5usize").unwrap(), Type::Int(IntType::Usize));
+    assert_eq!(infer_type("-7isize").unwrap(), Type::Int(IntType::Isize));
+    assert_eq!(infer_type("1_000usize").unwrap(), Type::Int(IntType::Usize));
+    assert_eq!(infer_type("0xE").unwrap(), Type::Int(IntType::I32));
+    assert_eq!(infer_type("0x1eu8").unwrap(), Type::Int(IntType::U8));
+    assert!(bits_command(&args("5usize")).is_ok());
+    assert!(bits_command(&args("5isize")).is_ok());
+}
+
+#[test]
+fn floats_are_told_apart_by_point_exponent_or_suffix() {
+    assert_eq!(infer_type("2.0").unwrap(), Type::Float(FloatType::F64));
+    assert_eq!(infer_type("1e3").unwrap(), Type::Float(FloatType::F64));
+    assert_eq!(infer_type("-2.5E-3").unwrap(), Type::Float(FloatType::F64));
+    assert_eq!(infer_type("0.5f32").unwrap(), Type::Float(FloatType::F32));
+    assert_eq!(infer_type("7f64").unwrap(), Type::Float(FloatType::F64));
+    assert_eq!(infer_type("inf").unwrap(), Type::Float(FloatType::F64));
+    assert_eq!(infer_type("NaN").unwrap(), Type::Float(FloatType::F64));
+}
+
+#[test]
+fn integers_default_like_the_compiler() {
+    assert_eq!(infer_type("-5").unwrap(), Type::Int(IntType::I32));
+    assert_eq!(infer_type("255u8").unwrap(), Type::Int(IntType::U8));
+    assert_eq!(infer_type("b'A'").unwrap(), Type::Int(IntType::U8));
+    assert!(infer_type("256u8").is_err());
+    assert!(infer_type("five").is_err());
+}
+
+#[test]
+fn types_can_be_forced() {
+    let with_type = |value: &str, ty: &str| {
+        bits_command(&[value.to_string(), "--type".to_string(), ty.to_string()])
+    };
+    assert!(with_type("-5", "i8").is_ok());
+    assert!(with_type("300", "u8").is_err());
+    assert!(with_type("0.1", "f32").is_ok());
+    assert!(with_type("1", "u7").is_err());
+}
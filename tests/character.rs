@@ -0,0 +1,117 @@
+use std::fs;
+
+use cpc::character::{
+    standing, total_xp_for_level, total_xp_for_paragon, xp_for_level, xp_for_paragon, Character,
+    CharacterError, Profiles, Standing, MAX_LEVEL, MAX_PARAGON,
+};
+
+#[test]
+fn curve_totals_add_up_the_steps() {
+    assert_eq!(total_xp_for_level(1), 0);
+    assert_eq!(total_xp_for_level(2), xp_for_level(1));
+    for level in 2..MAX_LEVEL {
+        assert_eq!(
+            total_xp_for_level(level + 1) - total_xp_for_level(level),
+            xp_for_level(level)
+        );
+        assert!(xp_for_level(level) > xp_for_level(level - 1));
+    }
+
+    assert_eq!(total_xp_for_paragon(0), total_xp_for_level(MAX_LEVEL));
+    for paragon in 0..MAX_PARAGON {
+        assert_eq!(
+            total_xp_for_paragon(paragon + 1) - total_xp_for_paragon(paragon),
+            xp_for_paragon(paragon)
+        );
+    }
+}
+
+#[test]
+fn standing_changes_exactly_at_each_threshold() {
+    for level in 2..=MAX_LEVEL {
+        let total = total_xp_for_level(level);
+        assert_eq!(standing(total).level, level);
+        assert_eq!(standing(total).into_next, 0);
+        assert_eq!(standing(total - 1).level, level - 1);
+    }
+
+    // A u8 couldn't hold paragon levels this high.
+    for paragon in [1, 255, 256, MAX_PARAGON] {
+        let total = total_xp_for_paragon(paragon);
+        assert_eq!(standing(total).paragon, paragon);
+        assert_eq!(standing(total - 1).paragon, paragon - 1);
+    }
+
+    assert_eq!(
+        standing(u64::MAX),
+        Standing {
+            level: MAX_LEVEL,
+            paragon: MAX_PARAGON,
+            into_next: u64::MAX - total_xp_for_paragon(MAX_PARAGON),
+            next: None,
+        }
+    );
+}
+
+#[test]
+fn adding_xp_levels_up_and_never_overflows() {
+    let mut character = Character::new("Ayla").unwrap();
+    assert_eq!((character.level(), character.paragon()), (1, 0));
+
+    let before = character.add_xp(total_xp_for_level(10)).unwrap();
+    assert_eq!(before.level, 1);
+    assert_eq!(character.level(), 10);
+
+    character.add_xp(total_xp_for_paragon(300)).unwrap();
+    assert_eq!((character.level(), character.paragon()), (60, 300));
+
+    let xp = character.xp();
+    assert_eq!(
+        character.add_xp(u64::MAX),
+        Err(CharacterError::XpOverflow {
+            xp,
+            gained: u64::MAX
+        })
+    );
+    assert_eq!(character.xp(), xp);
+}
+
+#[test]
+fn names_have_to_fit_on_a_line() {
+    assert_eq!(Character::new("  Bo Kin ").unwrap().name(), "Bo Kin");
+    for name in ["", "   ", "two\nlines", &"x".repeat(33)] {
+        assert!(matches!(
+            Character::new(name),
+            Err(CharacterError::InvalidName(_))
+        ));
+    }
+}
+
+#[test]
+fn profiles_survive_a_reload() {
+    let path = std::env::temp_dir().join(format!("cpc-characters-{}", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let mut profiles = Profiles::load(path.clone()).unwrap();
+    assert_eq!(profiles.characters().count(), 0);
+    profiles
+        .save_character(Character::with_xp("Ayla", 7_021_000).unwrap())
+        .unwrap();
+    profiles
+        .save_character(Character::new("Bo Kin").unwrap())
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "Ayla 7021000\nBo Kin 0\n"
+    );
+
+    let mut profiles = Profiles::load(path.clone()).unwrap();
+    assert_eq!(profiles.get("Ayla").unwrap().level(), MAX_LEVEL);
+    assert_eq!(profiles.get("Bo Kin").unwrap().xp(), 0);
+    assert!(profiles.remove("Bo Kin").unwrap());
+    assert!(!profiles.remove("Bo Kin").unwrap());
+
+    fs::write(&path, "Ayla lots\n").unwrap();
+    assert!(Profiles::load(path.clone()).is_err());
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,75 @@
+use cpc::chars::graphemes;
+use cpc::general_category::{general_category, GeneralCategory};
+
+#[test]
+fn categories_come_from_the_table() {
+    let expected = [
+        ('z', "Ll"),
+        ('ℤ', "Lu"),
+        ('🤗', "So"),
+        ('5', "Nd"),
+        ('Ⅻ', "Nl"),
+        ('_', "Pc"),
+        ('€', "Sc"),
+        ('+', "Sm"),
+        (' ', "Zs"),
+        ('\u{2028}', "Zl"),
+        ('\n', "Cc"),
+        ('\u{200D}', "Cf"),
+        ('\u{0301}', "Mn"),
+        ('\u{0903}', "Mc"),
+        ('\u{E000}', "Co"),
+        ('\u{0378}', "Cn"),
+        ('\u{10FFFF}', "Cn"),
+    ];
+
+    for (c, abbreviation) in expected {
+        assert_eq!(
+            general_category(c).abbreviation(),
+            abbreviation,
+            "U+{:04X}",
+            c as u32
+        );
+    }
+    assert_eq!(general_category('\0'), GeneralCategory::Control);
+    assert_eq!(GeneralCategory::UppercaseLetter.name(), "Uppercase_Letter");
+}
+
+#[test]
+fn emoji_sequences_are_one_grapheme() {
+    // man, ZWJ, woman, ZWJ, girl: five chars, one family.
+    let family = "👨\u{200D}👩\u{200D}👧";
+    assert_eq!(family.chars().count(), 5);
+    assert_eq!(graphemes(family), [family]);
+
+    assert_eq!(graphemes("👍🏽!"), ["👍🏽", "!"]);
+    assert_eq!(graphemes("e\u{301}z"), ["e\u{301}", "z"]);
+    assert_eq!(
+        graphemes("🏴\u{E0067}\u{E0062}\u{E0065}\u{E006E}\u{E0067}\u{E007F}").len(),
+        1
+    );
+
+    // A ZWJ only joins pictographs, after a letter it just extends it.
+    assert_eq!(graphemes("a\u{200D}b"), ["a\u{200D}", "b"]);
+}
+
+#[test]
+fn flags_pair_up_regional_indicators() {
+    assert_eq!(graphemes("🇺🇸🇫🇷"), ["🇺🇸", "🇫🇷"]);
+    assert_eq!(graphemes("🇺🇸🇫"), ["🇺🇸", "🇫"]);
+    assert_eq!(graphemes("x🇯🇵"), ["x", "🇯🇵"]);
+}
+
+#[test]
+fn crlf_is_one_grapheme_but_controls_stand_alone() {
+    assert_eq!(graphemes("a\r\nb"), ["a", "\r\n", "b"]);
+    assert_eq!(graphemes("\r\r\n\n"), ["\r", "\r\n", "\n"]);
+    assert_eq!(graphemes("\n\u{301}"), ["\n", "\u{301}"]);
+
+    // Format characters count as controls too, except the two joiners.
+    assert_eq!(graphemes("\u{200B}\u{301}"), ["\u{200B}", "\u{301}"]);
+    assert_eq!(graphemes("\u{FEFF}\u{301}"), ["\u{FEFF}", "\u{301}"]);
+    assert_eq!(graphemes("a\u{AD}b"), ["a", "\u{AD}", "b"]);
+    assert_eq!(graphemes("a\u{200C}b"), ["a\u{200C}", "b"]);
+    assert_eq!(graphemes(""), [] as [&str; 0]);
+}
@@ -0,0 +1,131 @@
+use cpc::data_types::{index_or_panic, index_prompt};
+use cpc::ints::IntType;
+use cpc::scalar::{parse_scalar, ScalarError};
+
+const ARRAY: [i32; 5] = [1, 2, 3, 4, 5];
+
+fn prompt(input: &str) -> (Option<(usize, i32)>, String) {
+    let mut output = Vec::new();
+    let found = index_prompt(input.as_bytes(), &mut output, &ARRAY).unwrap();
+    (found, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn prompt_returns_the_element_at_a_valid_index() {
+    assert_eq!(prompt("0\n").0, Some((0, 1)));
+    assert_eq!(prompt(" 4 \n").0, Some((4, 5)));
+}
+
+#[test]
+fn prompt_asks_again_instead_of_panicking() {
+    let (found, output) = prompt("5\n10\nabc\n-1\n\n2\n");
+
+    assert_eq!(found, Some((2, 3)));
+    assert_eq!(output.matches("Please enter an array index").count(), 6);
+    assert!(output.contains("Index 5 is out of bounds, the array only has 5 elements."));
+    assert!(output.contains("Index 10 is out of bounds"));
+    assert!(output.contains("`abc` is not a valid usize"));
+    assert!(output.contains("An index can't be negative"));
+    assert!(output.contains("nothing was entered"));
+}
+
+#[test]
+fn prompt_stops_on_quit_or_end_of_input() {
+    assert_eq!(prompt("7\nquit\n3\n").0, None);
+    assert_eq!(prompt("7\n").0, None);
+    assert_eq!(prompt("").0, None);
+}
+
+#[test]
+fn indexing_past_the_end_panics_with_the_std_message() {
+    assert_eq!(index_or_panic(&ARRAY, 1), Ok(2));
+    assert_eq!(
+        index_or_panic(&ARRAY, 10),
+        Err(String::from(
+            "index out of bounds: the len is 5 but the index is 10"
+        ))
+    );
+    assert_eq!(
+        index_or_panic(&ARRAY, 5),
+        Err(String::from(
+            "index out of bounds: the len is 5 but the index is 5"
+        ))
+    );
+}
+
+#[test]
+fn scalars_parse_like_str_parse() {
+    assert_eq!(parse_scalar::<u8>(" 27\n"), Ok(27));
+    assert_eq!(parse_scalar::<i64>("-9000"), Ok(-9000));
+    assert_eq!(parse_scalar::<f32>("2.5"), Ok(2.5));
+    assert_eq!(parse_scalar::<bool>("true"), Ok(true));
+    assert_eq!(parse_scalar::<char>("🤗"), Ok('🤗'));
+}
+
+#[test]
+fn out_of_range_integers_suggest_a_wider_type() {
+    let overflow = parse_scalar::<u8>("300").unwrap_err();
+    assert_eq!(
+        overflow,
+        ScalarError::Overflow {
+            text: String::from("300"),
+            ty: IntType::U8,
+            suggestion: Some(IntType::U16),
+        }
+    );
+    assert_eq!(overflow.to_string(), "300 exceeds u8::MAX (255), try u16");
+
+    assert_eq!(
+        parse_scalar::<i8>("-200").unwrap_err().to_string(),
+        "-200 is below i8::MIN (-128), try i16"
+    );
+    assert!(matches!(
+        parse_scalar::<u8>("-5"),
+        Err(ScalarError::Underflow {
+            ty: IntType::U8,
+            suggestion: Some(IntType::I8),
+            ..
+        })
+    ));
+    assert_eq!(
+        parse_scalar::<u128>("340282366920938463463374607431768211456")
+            .unwrap_err()
+            .to_string(),
+        "340282366920938463463374607431768211456 exceeds u128::MAX \
+         (340282366920938463463374607431768211455), no integer type can hold it"
+    );
+}
+
+#[test]
+fn every_other_mistake_has_its_own_error() {
+    assert_eq!(
+        parse_scalar::<u8>("  "),
+        Err(ScalarError::Empty { ty: "u8" })
+    );
+    assert_eq!(
+        parse_scalar::<u8>("2x7"),
+        Err(ScalarError::InvalidDigit {
+            text: String::from("2x7"),
+            digit: 'x',
+            ty: IntType::U8,
+        })
+    );
+    assert_eq!(
+        parse_scalar::<f64>("1.2.3"),
+        Err(ScalarError::InvalidFloat {
+            text: String::from("1.2.3"),
+            ty: "f64",
+        })
+    );
+    assert_eq!(
+        parse_scalar::<bool>("yes"),
+        Err(ScalarError::InvalidBool(String::from("yes")))
+    );
+    assert_eq!(
+        parse_scalar::<char>("ab"),
+        Err(ScalarError::MultiChar {
+            text: String::from("ab"),
+            count: 2,
+        })
+    );
+}
@@ -0,0 +1,109 @@
+use cpc::destructure::{run_let, LetError, MAX_SCALARS};
+
+// name: type = value, for every binding in order.
+fn bindings(text: &str) -> Vec<String> {
+    run_let(text)
+        .unwrap()
+        .bindings
+        .iter()
+        .map(|binding| format!("{}: {} = {}", binding.name, binding.ty, binding.value))
+        .collect()
+}
+
+#[test]
+fn tuples_and_arrays_bind_their_parts() {
+    assert_eq!(
+        bindings("let (x, _, z) = (500, 6.4, 1u8)"),
+        ["x: i32 = 500", "z: u8 = 1"]
+    );
+    assert_eq!(
+        bindings("let [a, .., e] = [1, 2, 3, 4, 5]"),
+        ["a: i32 = 1", "e: i32 = 5"]
+    );
+    assert_eq!(
+        bindings("let [first, rest @ ..] = [3u16; 3];"),
+        ["first: u16 = 3", "rest: [u16; 2] = [3, 3]"]
+    );
+    assert_eq!(
+        bindings("let (x, y): (i64, f32) = (5, 1.5)"),
+        ["x: i64 = 5", "y: f32 = 1.5"]
+    );
+}
+
+#[test]
+fn sizes_match_size_of() {
+    let sized = |text: &str| {
+        let result = run_let(text).unwrap();
+        (result.ty.to_string(), result.ty.size(), result.ty.align())
+    };
+
+    assert_eq!(
+        sized("let t = (500, 6.4, 1u8)"),
+        (String::from("(i32, f64, u8)"), Some(16), 8)
+    );
+    assert_eq!(
+        sized("let a = [3; 5]"),
+        (String::from("[i32; 5]"), Some(20), 4)
+    );
+    assert_eq!(
+        sized("let c = ('x', true)"),
+        (String::from("(char, bool)"), Some(8), 4)
+    );
+    assert_eq!(sized("let u = ()"), (String::from("()"), Some(0), 1));
+}
+
+#[test]
+fn mismatched_patterns_are_rejected() {
+    // Like rustc, the value's type is what's expected.
+    assert!(matches!(
+        run_let("let (a, b) = (1, 2, 3)"),
+        Err(LetError::TupleArity {
+            expected: 3,
+            found: 2
+        })
+    ));
+    assert!(matches!(
+        run_let("let [a, b] = [1, 2, 3]"),
+        Err(LetError::ArrayLength {
+            required: 2,
+            length: 3,
+            rest: false
+        })
+    ));
+    assert!(matches!(
+        run_let("let [a, b, c, ..] = [1; 2]"),
+        Err(LetError::ArrayLength { rest: true, .. })
+    ));
+    assert!(matches!(
+        run_let("let [a] = (1,)"),
+        Err(LetError::NotAnArray(_))
+    ));
+    assert!(matches!(
+        run_let("let (a, a) = (1, 2)"),
+        Err(LetError::DuplicateBinding(_))
+    ));
+    assert!(matches!(
+        run_let("let x: (i32, u8) = (1, 2.0)"),
+        Err(LetError::MismatchedTypes { .. })
+    ));
+    assert!(matches!(
+        run_let("let x = []"),
+        Err(LetError::AnnotationsNeeded(_))
+    ));
+}
+
+#[test]
+fn huge_arrays_are_rejected_before_they_are_built() {
+    assert_eq!(
+        run_let("let x = [0u64; 3000000000000000000]").unwrap_err(),
+        LetError::ArrayTooLong(3_000_000_000_000_000_000)
+    );
+    assert!(matches!(
+        run_let("let x = [[0u8; 300]; 300]"),
+        Err(LetError::TooLarge(_))
+    ));
+    assert!(run_let("let x: [u8; 18446744073709551615] = [0; 3]").is_err());
+
+    let largest = format!("let x = [0u8; {MAX_SCALARS}]");
+    assert_eq!(run_let(&largest).unwrap().ty.size(), Some(MAX_SCALARS));
+}
@@ -0,0 +1,124 @@
+use cpc::eval::{evaluate, type_of, BinOp, EvalError, Ty, Value};
+use cpc::floats::{FloatType, FloatValue};
+use cpc::ints::{IntType, IntValue};
+
+// The value and type of a successful evaluation, printed like cpc eval.
+fn result(text: &str) -> String {
+    let value = evaluate(text).unwrap().value;
+    format!("{value} ({})", value.ty())
+}
+
+#[test]
+fn integers_follow_rust_semantics() {
+    assert_eq!(result("-5 / 3"), "-1 (i32)");
+    assert_eq!(result("43 % 5"), "3 (i32)");
+    assert_eq!(result("-43 % 5"), "-3 (i32)");
+    assert_eq!(result("1 + 2 * 3"), "7 (i32)");
+    assert_eq!(result("-128i8"), "-128 (i8)");
+    assert_eq!(result("5u8 + 250"), "255 (u8)");
+
+    let notes = evaluate("-5 / 3").unwrap().notes;
+    assert!(notes.iter().any(|note| note.contains("default to i32")));
+    assert!(notes
+        .iter()
+        .any(|note| note.contains("truncates toward zero")));
+}
+
+#[test]
+fn floats_default_to_f64() {
+    assert_eq!(
+        evaluate("56.7 / 32.2").unwrap().value,
+        Value::Float(FloatValue::F64(56.7 / 32.2))
+    );
+    assert_eq!(
+        evaluate("5.0f32 + 10.9").unwrap().value,
+        Value::Float(FloatValue::F32(5.0 + 10.9))
+    );
+    assert_eq!(type_of("2.0 * 3.0").unwrap(), Ty::FloatVar);
+}
+
+#[test]
+fn mismatched_types_are_rejected() {
+    assert_eq!(
+        evaluate("1 + 2.0").unwrap_err(),
+        EvalError::Mismatched {
+            op: BinOp::Add,
+            lhs: Ty::IntVar,
+            rhs: Ty::FloatVar
+        }
+    );
+    assert_eq!(
+        evaluate("1i32 + 2.0f64").unwrap_err().to_string(),
+        "cannot add `f64` to `i32` (E0277)"
+    );
+    assert!(matches!(
+        evaluate("1u8 * 2i64"),
+        Err(EvalError::Mismatched { .. })
+    ));
+}
+
+#[test]
+fn overflow_and_division_by_zero_are_errors() {
+    assert!(matches!(
+        evaluate("2147483647 + 1"),
+        Err(EvalError::Overflow(_))
+    ));
+    assert!(matches!(
+        evaluate("-128i8 / -1"),
+        Err(EvalError::Overflow(_))
+    ));
+    assert!(matches!(evaluate("1 / 0"), Err(EvalError::Panic(_))));
+    assert!(matches!(evaluate("7 % 0"), Err(EvalError::Panic(_))));
+    assert!(matches!(evaluate("5u8 + 300"), Err(EvalError::Literal(_))));
+    assert_eq!(
+        evaluate("-1u8").unwrap_err(),
+        EvalError::NegateUnsigned(Ty::Int(IntType::U8))
+    );
+}
+
+#[test]
+fn literals_take_the_type_they_are_cast_to() {
+    assert_eq!(
+        evaluate("255 as u8").unwrap().value,
+        Value::Int(IntValue::U8(255))
+    );
+    assert!(evaluate("255 as u8").unwrap().notes.is_empty());
+    assert!(evaluate("2.5 as f32").unwrap().notes.is_empty());
+    assert_eq!(
+        evaluate("-1 as u8").unwrap_err(),
+        EvalError::NegateUnsigned(Ty::Int(IntType::U8))
+    );
+    assert!(matches!(evaluate("300 as u8"), Err(EvalError::Literal(_))));
+
+    // Anything that isn't a bare literal is still typed first, then cast.
+    let wrapped = evaluate("(200 + 100) as u8").unwrap();
+    assert_eq!(wrapped.value, Value::Int(IntValue::U8(44)));
+    assert!(wrapped.notes.iter().any(|note| note.contains("i32")));
+    assert_eq!(
+        evaluate("3.9 as u8").unwrap().value,
+        Value::Int(IntValue::U8(3))
+    );
+}
+
+#[test]
+fn float_literals_that_round_to_infinity_are_rejected() {
+    assert_eq!(
+        evaluate("1e400").unwrap_err(),
+        EvalError::FloatOutOfRange {
+            literal: String::from("1e400"),
+            ty: FloatType::F64
+        }
+    );
+    assert!(matches!(
+        evaluate("1e39f32"),
+        Err(EvalError::FloatOutOfRange {
+            ty: FloatType::F32,
+            ..
+        })
+    ));
+    // Only the literal is checked, arithmetic may still reach infinity.
+    assert_eq!(
+        evaluate("1e300 * 1e300").unwrap().value,
+        Value::Float(FloatValue::F64(f64::INFINITY))
+    );
+}
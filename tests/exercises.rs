@@ -0,0 +1,58 @@
+use cpc::exercises::{conversion_tables, convert, lyrics, nth_fibonacci};
+use cpc::temperature::{Scale, Temperature};
+
+fn temperature(value: &str, scale: Scale) -> Temperature {
+    Temperature::new(value.parse().unwrap(), scale).unwrap()
+}
+
+#[test]
+fn twelve_days_matches_the_song() {
+    assert_eq!(lyrics(12), include_str!("golden/twelve_days.txt"));
+}
+
+#[test]
+fn fewer_days_are_the_start_of_the_song() {
+    assert_eq!(
+        lyrics(1),
+        "On the first day of Christmas my true love sent to me\n\
+         A partridge in a pear tree\n"
+    );
+
+    let song = include_str!("golden/twelve_days.txt");
+    for days in 1..=12 {
+        assert!(song.starts_with(&lyrics(days)), "{days} days");
+    }
+}
+
+#[test]
+fn nth_fibonacci_matches_known_output() {
+    assert_eq!(
+        nth_fibonacci(10),
+        include_str!("golden/nth_fibonacci_10.txt")
+    );
+    assert_eq!(
+        nth_fibonacci(100),
+        include_str!("golden/nth_fibonacci_100.txt")
+    );
+    assert_eq!(
+        nth_fibonacci(0),
+        "F(0) = 0\nThe Fibonnacci number at index 0 is 0.\n"
+    );
+}
+
+#[test]
+fn temperature_tables_match_known_output() {
+    assert_eq!(conversion_tables(), include_str!("golden/temperatures.txt"));
+}
+
+#[test]
+fn temperatures_convert_to_the_other_scale() {
+    assert_eq!(
+        convert(temperature("98.6", Scale::Fahrenheit)),
+        "°C = (°F − 32) × 5/9\n98.6 °F = 37 °C\n"
+    );
+    assert_eq!(
+        convert(temperature("-40", Scale::Celsius)),
+        "°F = °C × 9/5 + 32\n-40 °C = -40 °F\n"
+    );
+}
@@ -0,0 +1,305 @@
+use std::ops::RangeInclusive;
+
+use cpc::bignum::BigUint;
+use cpc::fibonnacci::{
+    fib_mod, fibonnacci, fibonnacci_big, fibonnacci_big_with, fibonnacci_with, index_of,
+    is_fibonacci, pisano_period, prompt, sum_range, BigFibonacci, FibAlgorithm, FibIndexing,
+    Fibonacci, MAX_U64_N,
+};
+
+const INDEXINGS: [FibIndexing; 2] = [FibIndexing::Standard, FibIndexing::Classic];
+
+// Small xorshift generator so the property tests are repeatable without
+// pulling in a crate.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, bound: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % bound
+    }
+}
+
+fn big_nth(indexing: FibIndexing, n: u64, algorithm: FibAlgorithm) -> BigUint {
+    let n = indexing
+        .to_standard(n)
+        .expect("index is valid for the numbering");
+    fibonnacci_big_with(n, algorithm)
+}
+
+#[test]
+fn standard_numbering_starts_at_zero() {
+    let expected = [0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55];
+
+    for (n, &want) in expected.iter().enumerate() {
+        for algorithm in FibAlgorithm::ALL {
+            assert_eq!(
+                fibonnacci_with(n as u64, algorithm),
+                Some(want),
+                "{algorithm} F({n})"
+            );
+        }
+    }
+}
+
+#[test]
+fn classic_numbering_starts_at_one() {
+    let classic = FibIndexing::Classic;
+
+    assert_eq!(classic.to_standard(0), None);
+    assert_eq!(classic.to_standard(1).and_then(fibonnacci), Some(1));
+    assert_eq!(classic.to_standard(2).and_then(fibonnacci), Some(1));
+    assert_eq!(classic.to_standard(3).and_then(fibonnacci), Some(2));
+    assert_eq!(FibIndexing::Standard.to_standard(0), Some(0));
+}
+
+#[test]
+fn recurrence_holds_for_every_u64_result() {
+    for indexing in INDEXINGS {
+        for algorithm in FibAlgorithm::ALL {
+            for n in indexing.first_index()..=MAX_U64_N - 2 {
+                let f = |n| {
+                    indexing
+                        .to_standard(n)
+                        .and_then(|n| fibonnacci_with(n, algorithm))
+                        .unwrap()
+                };
+
+                assert_eq!(
+                    f(n + 2),
+                    f(n + 1) + f(n),
+                    "{indexing:?} {algorithm} n = {n}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn recurrence_holds_for_big_results() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+    for indexing in INDEXINGS {
+        for algorithm in FibAlgorithm::ALL {
+            for _ in 0..25 {
+                let n = indexing.first_index() + rng.below(3_000);
+
+                let sum = &big_nth(indexing, n + 1, algorithm) + &big_nth(indexing, n, algorithm);
+                assert_eq!(
+                    big_nth(indexing, n + 2, algorithm),
+                    sum,
+                    "{indexing:?} {algorithm} n = {n}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn algorithms_agree() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..50 {
+        let n = rng.below(5_000);
+        let expected = fibonnacci_big(n);
+
+        for algorithm in FibAlgorithm::ALL {
+            assert_eq!(
+                fibonnacci_big_with(n, algorithm),
+                expected,
+                "{algorithm} n = {n}"
+            );
+        }
+    }
+}
+
+#[test]
+fn u64_path_stops_at_overflow() {
+    for algorithm in FibAlgorithm::ALL {
+        assert_eq!(
+            fibonnacci_with(MAX_U64_N, algorithm),
+            Some(12_200_160_415_121_876_738)
+        );
+        assert_eq!(fibonnacci_with(MAX_U64_N + 1, algorithm), None);
+    }
+
+    for n in 0..=MAX_U64_N {
+        assert_eq!(fibonnacci_big(n).to_u64(), fibonnacci(n));
+    }
+
+    assert_eq!(
+        fibonnacci_big(MAX_U64_N + 1).to_string(),
+        "19740274219868223167"
+    );
+}
+
+#[test]
+fn default_algorithm_reaches_hundreds_of_thousands() {
+    assert_eq!(FibAlgorithm::default(), FibAlgorithm::Doubling);
+
+    let f = fibonnacci_big_with(300_000, FibAlgorithm::default()).to_string();
+    assert_eq!(f.len(), 62_696);
+    assert!(f.starts_with("87617325329163457942"));
+    assert!(f.ends_with("96111233670979600000"));
+}
+
+#[test]
+fn iterators_yield_the_sequence() {
+    let numbers: Vec<u64> = Fibonacci::new().collect();
+    assert_eq!(numbers.len() as u64, MAX_U64_N + 1);
+    assert_eq!(numbers[..8], [0, 1, 1, 2, 3, 5, 8, 13]);
+    assert_eq!(numbers.last(), fibonnacci(MAX_U64_N).as_ref());
+
+    for (small, big) in Fibonacci::new().zip(BigFibonacci::new()) {
+        assert_eq!(big.to_u64(), Some(small));
+    }
+    assert_eq!(
+        BigFibonacci::new().nth(100).unwrap().to_string(),
+        "354224848179261915075"
+    );
+
+    assert_eq!(
+        Fibonacci::new().take_until(100).collect::<Vec<_>>(),
+        [0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89]
+    );
+    assert_eq!(Fibonacci::new().take_until(u64::MAX).count(), numbers.len());
+}
+
+#[test]
+fn index_of_finds_the_first_position() {
+    assert_eq!(index_of(0), Some(0));
+    assert_eq!(index_of(1), Some(1));
+    assert_eq!(index_of(144), Some(12));
+    assert_eq!(index_of(4), None);
+    assert_eq!(index_of(12_200_160_415_121_876_738), Some(MAX_U64_N));
+
+    assert!(is_fibonacci(89));
+    assert!(!is_fibonacci(90));
+    assert!(!is_fibonacci(u64::MAX));
+}
+
+#[test]
+fn range_sums_match_adding_one_by_one() {
+    for (start, end) in [(0, 0), (0, 10), (3, 5), (10, 90), (1, MAX_U64_N)] {
+        let mut expected = BigUint::zero();
+        for number in BigFibonacci::new()
+            .skip(start as usize)
+            .take((end - start + 1) as usize)
+        {
+            expected += &number;
+        }
+        assert_eq!(sum_range(start..=end), Some(expected), "{start}..={end}");
+    }
+
+    assert_eq!(sum_range(RangeInclusive::new(5, 3)), Some(BigUint::zero()));
+    assert_eq!(sum_range(0..=u64::MAX), None);
+    assert_eq!(sum_range(u64::MAX - 1..=u64::MAX - 1), None);
+}
+
+// The period found by walking the sequence mod m until it is back at 0, 1.
+fn walked_period(m: u64) -> u128 {
+    let (mut a, mut b) = (0, 1 % m);
+    let mut period = 0;
+    loop {
+        (a, b) = (b, (a + b) % m);
+        period += 1;
+        if (a, b) == (0, 1 % m) {
+            return period;
+        }
+    }
+}
+
+#[test]
+fn fib_mod_matches_the_big_numbers() {
+    for m in [1, 2, 7, 10, 1_000_000_007, u64::MAX] {
+        for (n, number) in BigFibonacci::new().take(300).enumerate() {
+            assert_eq!(
+                fib_mod(n as u128, m),
+                Some(number.rem_u64(m)),
+                "F({n}) mod {m}"
+            );
+        }
+    }
+
+    // Huge n only depend on where they fall in the period.
+    let period = pisano_period(1_000).unwrap();
+    assert_eq!(
+        fib_mod(u128::MAX, 1_000),
+        fib_mod(u128::MAX % period, 1_000)
+    );
+    assert_eq!(fib_mod(10, 0), None);
+}
+
+#[test]
+fn pisano_periods_match_walking_the_sequence() {
+    assert_eq!(pisano_period(0), None);
+    assert_eq!(pisano_period(1), Some(1));
+    assert_eq!(pisano_period(2), Some(3));
+    assert_eq!(pisano_period(3), Some(8));
+    assert_eq!(pisano_period(5), Some(20));
+    assert_eq!(pisano_period(10), Some(60));
+    assert_eq!(pisano_period(1_000_000_000), Some(1_500_000_000));
+
+    for m in 1..=500 {
+        assert_eq!(pisano_period(m), Some(walked_period(m)), "m = {m}");
+    }
+}
+
+fn prompted(input: &str, indexing: FibIndexing) -> String {
+    let mut output = Vec::new();
+    prompt(
+        input.as_bytes(),
+        &mut output,
+        indexing,
+        FibAlgorithm::default(),
+    )
+    .unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn prompt_explains_bad_input_and_asks_again() {
+    let output = prompted(
+        "abc\n\n-3\n99999999999999999999999\n2000000\n10\n",
+        FibIndexing::Standard,
+    );
+
+    assert_eq!(output.matches("Please enter which").count(), 7);
+    assert!(output.contains("`abc` is not a valid u64"));
+    assert!(output.contains("Please type a number."));
+    assert!(output.contains("no Fibonnacci numbers at negative positions"));
+    assert!(output.contains("99999999999999999999999 is out of range"));
+    assert!(output.contains("2000000 is out of range, please enter a number up to 1000000."));
+    assert!(output.ends_with(
+        "The Fibonnacci number you are looking for is: 55\n\
+         Please enter which Fibonnacci number you want to find (or quit):\n"
+    ));
+
+    let classic = prompted("0\n1\n", FibIndexing::Classic);
+    assert!(classic.contains("The classic sequence starts at 1"));
+    assert!(classic.contains("looking for is: 1\n"));
+}
+
+#[test]
+fn prompt_switches_to_big_integers_and_stops_on_quit() {
+    let output = prompted("94\nquit\n5\n", FibIndexing::Standard);
+    assert!(output.contains("F(94) is too large for a u64, switching to big integers."));
+    assert!(output.contains("looking for is: 19740274219868223167"));
+    assert!(!output.contains("looking for is: 5"));
+    assert_eq!(output.matches("Please enter which").count(), 2);
+
+    assert_eq!(
+        prompted("Q\n", FibIndexing::Standard)
+            .matches("Please")
+            .count(),
+        1
+    );
+    assert_eq!(
+        prompted("", FibIndexing::Standard)
+            .matches("Please")
+            .count(),
+        1
+    );
+}
@@ -0,0 +1,77 @@
+use cpc::decimal::Decimal;
+use cpc::floats::{FloatOp, FloatType, FloatValue};
+
+fn decimal(text: &str) -> Decimal {
+    text.parse().unwrap()
+}
+
+// The exact value `text` is stored as in `ty`.
+fn stored(text: &str, ty: FloatType) -> Decimal {
+    FloatValue::parse(text, ty).unwrap().exact().unwrap()
+}
+
+#[test]
+fn tenths_are_not_stored_exactly() {
+    assert_eq!(
+        stored("0.1", FloatType::F64),
+        decimal("0.1000000000000000055511151231257827021181583404541015625")
+    );
+    assert_eq!(
+        stored("0.1", FloatType::F32),
+        decimal("0.100000001490116119384765625")
+    );
+    assert_eq!(stored("0.5", FloatType::F64), decimal("0.5"));
+}
+
+#[test]
+fn a_sum_can_round_exactly_and_still_be_off() {
+    let (a, b) = (
+        stored("5.0", FloatType::F64),
+        stored("10.9", FloatType::F64),
+    );
+    let sum = FloatOp::Add
+        .apply(
+            FloatValue::parse("5.0", FloatType::F64).unwrap(),
+            FloatValue::parse("10.9", FloatType::F64).unwrap(),
+        )
+        .exact()
+        .unwrap();
+
+    // Adding the stored values rounds nothing, the error came from 10.9.
+    assert_eq!(FloatOp::Add.error(&a, &b, &sum), None);
+    let on_paper = FloatOp::Add
+        .error(&decimal("5.0"), &decimal("10.9"), &sum)
+        .unwrap();
+    assert_eq!(on_paper.exponent, -16);
+    assert!(on_paper.mantissa > 0.0);
+}
+
+#[test]
+fn thirds_are_rounded_by_the_division() {
+    let third = stored("0.3333333333333333", FloatType::F64);
+    let error = FloatOp::Div
+        .error(&decimal("1"), &decimal("3"), &third)
+        .unwrap();
+    assert_eq!(error.exponent, -17);
+    assert!((error.mantissa + 1.8503717077085942).abs() < 1e-12);
+
+    assert_eq!(
+        FloatOp::Div.error(&decimal("1"), &decimal("4"), &decimal("0.25")),
+        None
+    );
+}
+
+#[test]
+fn subnormal_errors_do_not_round_to_zero() {
+    let smallest = stored("5e-324", FloatType::F64);
+    assert_eq!(smallest, Decimal::from_f64(f64::from_bits(1)).unwrap());
+
+    // Less than the smallest f64 apart, which to_f64 can't tell from 0.
+    let error = &smallest - &decimal("5e-324");
+    assert_eq!(error.to_f64(), 0.0);
+    let error = error.to_scientific().unwrap();
+    assert_eq!(error.exponent, -326);
+    assert!(error.mantissa < 0.0);
+
+    assert_eq!((&smallest - &smallest).to_scientific(), None);
+}
@@ -0,0 +1,147 @@
+use std::fs;
+
+use cpc::lessons::{Context, Lesson, LessonError, Registry};
+use cpc::progress::Progress;
+
+// A lesson that does nothing, or fails when it's given any argument.
+struct Step {
+    id: &'static str,
+    prerequisites: &'static [&'static str],
+}
+
+impl Lesson for Step {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn title(&self) -> &'static str {
+        self.id
+    }
+
+    fn summary(&self) -> &'static str {
+        "a test lesson"
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        self.prerequisites
+    }
+
+    fn run(&self, ctx: &mut Context) -> Result<(), LessonError> {
+        ctx.expect_no_args(self.id)
+    }
+}
+
+fn registry() -> Registry {
+    let mut registry = Registry::new();
+    registry.register(Step {
+        id: "basics",
+        prerequisites: &[],
+    });
+    registry.register(Step {
+        id: "types",
+        prerequisites: &[],
+    });
+    registry.register(Step {
+        id: "functions",
+        prerequisites: &["basics", "types"],
+    });
+    registry
+}
+
+#[test]
+fn lessons_wait_for_their_prerequisites() {
+    let registry = registry();
+    let mut ctx = Context::new(Vec::new(), Progress::in_memory());
+
+    match registry.run("functions", &mut ctx) {
+        Err(LessonError::MissingPrerequisites { lesson, missing }) => {
+            assert_eq!(lesson, "functions");
+            assert_eq!(missing, ["basics", "types"]);
+        }
+        other => panic!("expected missing prerequisites, got {other:?}"),
+    }
+    assert!(!ctx.progress().is_completed("functions"));
+
+    registry.run("basics", &mut ctx).unwrap();
+    assert!(matches!(
+        registry.run("functions", &mut ctx),
+        Err(LessonError::MissingPrerequisites { missing, .. }) if missing == ["types"]
+    ));
+
+    registry.run("types", &mut ctx).unwrap();
+    registry.run("functions", &mut ctx).unwrap();
+    assert_eq!(
+        ctx.progress().completed().collect::<Vec<_>>(),
+        ["basics", "functions", "types"]
+    );
+}
+
+#[test]
+fn failed_lessons_are_not_completed() {
+    let registry = registry();
+    let mut ctx = Context::new(vec![String::from("extra")], Progress::in_memory());
+
+    assert!(matches!(
+        registry.run("basics", &mut ctx),
+        Err(LessonError::InvalidArgs(_))
+    ));
+    assert!(!ctx.progress().is_completed("basics"));
+    assert!(matches!(
+        registry.run("advanced", &mut ctx),
+        Err(LessonError::Unknown(_))
+    ));
+}
+
+#[test]
+fn completed_prerequisites_are_remembered_between_runs() {
+    let path = std::env::temp_dir().join(format!("cpc-progress-{}", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let registry = registry();
+
+    let mut ctx = Context::new(Vec::new(), Progress::load(path.clone()).unwrap());
+    registry.run("basics", &mut ctx).unwrap();
+    registry.run("types", &mut ctx).unwrap();
+
+    let mut ctx = Context::new(Vec::new(), Progress::load(path.clone()).unwrap());
+    registry.run("functions", &mut ctx).unwrap();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "basics\nfunctions\ntypes\n"
+    );
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn registration_keeps_the_curriculum_in_order() {
+    let mut registry = registry();
+
+    assert!(matches!(
+        registry.try_register(Step {
+            id: "basics",
+            prerequisites: &[],
+        }),
+        Err(LessonError::DuplicateId("basics"))
+    ));
+    assert!(matches!(
+        registry.try_register(Step {
+            id: "closures",
+            prerequisites: &["iterators"],
+        }),
+        Err(LessonError::UnknownPrerequisite {
+            lesson: "closures",
+            prerequisite: "iterators"
+        })
+    ));
+
+    let ids: Vec<&str> = registry.lessons().map(|lesson| lesson.id()).collect();
+    assert_eq!(ids, ["basics", "types", "functions"]);
+
+    // The shipped lessons only ever depend on earlier ones.
+    let curriculum = Registry::curriculum();
+    let mut seen = Vec::new();
+    for lesson in curriculum.lessons() {
+        assert!(lesson.prerequisites().iter().all(|id| seen.contains(id)));
+        seen.push(lesson.id());
+    }
+}
@@ -0,0 +1,118 @@
+use cpc::ints::{IntType, IntValue};
+use cpc::literal::{parse_literal, parse_literal_as, LiteralError};
+
+fn value(text: &str) -> IntValue {
+    parse_literal(text).unwrap().value
+}
+
+#[test]
+fn radixes_and_separators_match_rust() {
+    assert_eq!(value("98_222"), IntValue::I32(98_222));
+    assert_eq!(value("0xff"), IntValue::I32(0xff));
+    assert_eq!(value("0o77"), IntValue::I32(0o77));
+    assert_eq!(value("0b1111_0000"), IntValue::I32(0b1111_0000));
+    assert_eq!(value("-0x80"), IntValue::I32(-0x80));
+
+    let radixes: Vec<u32> = ["10", "0x10", "0o10", "0b10"]
+        .into_iter()
+        .map(|text| parse_literal(text).unwrap().radix)
+        .collect();
+    assert_eq!(radixes, [10, 16, 8, 2]);
+}
+
+#[test]
+fn suffixes_and_bytes_pick_the_type() {
+    assert_eq!(value("57u8"), IntValue::U8(57));
+    assert_eq!(value("1_000i64"), IntValue::I64(1_000));
+    assert_eq!(value("0b1111_0000u16"), IntValue::U16(0b1111_0000));
+    assert_eq!(value("0xffu8"), IntValue::U8(255));
+    assert_eq!(value("-128i8"), IntValue::I8(-128));
+    assert_eq!(value("7usize"), IntValue::Usize(7));
+
+    let byte = parse_literal("b'A'").unwrap();
+    assert_eq!((byte.value, byte.byte), (IntValue::U8(65), true));
+    assert_eq!(value("b'\\n'"), IntValue::U8(b'\n'));
+    assert_eq!(value("b'\\x7f'"), IntValue::U8(0x7f));
+
+    assert_eq!(parse_literal_as("200", IntType::U8), Ok(IntValue::U8(200)));
+    assert_eq!(
+        parse_literal_as("200u16", IntType::U8),
+        Err(LiteralError::MismatchedTypes {
+            expected: IntType::U8,
+            found: IntType::U16
+        })
+    );
+}
+
+#[test]
+fn out_of_range_literals_suggest_a_type() {
+    assert_eq!(
+        parse_literal("256u8"),
+        Err(LiteralError::OutOfRange {
+            literal: String::from("256u8"),
+            ty: IntType::U8,
+            suggestion: Some(IntType::U16),
+        })
+    );
+    assert!(matches!(
+        parse_literal("-129i8"),
+        Err(LiteralError::OutOfRange {
+            ty: IntType::I8,
+            suggestion: Some(IntType::I16),
+            ..
+        })
+    ));
+    assert!(matches!(
+        parse_literal("2147483648"),
+        Err(LiteralError::OutOfRange {
+            ty: IntType::I32,
+            ..
+        })
+    ));
+    assert!(matches!(
+        parse_literal("340282366920938463463374607431768211456u128"),
+        Err(LiteralError::OutOfRange {
+            suggestion: None,
+            ..
+        })
+    ));
+    assert_eq!(
+        parse_literal_as("300", IntType::U8)
+            .unwrap_err()
+            .to_string(),
+        "literal out of range for `u8`: `300` does not fit into the type `u8` whose range is \
+         `0..=255`; consider using the type `u16` instead"
+    );
+}
+
+#[test]
+fn malformed_literals_are_rejected() {
+    assert_eq!(parse_literal("  "), Err(LiteralError::Empty));
+    assert_eq!(parse_literal("0x"), Err(LiteralError::NoDigits));
+    assert_eq!(
+        parse_literal("0b102"),
+        Err(LiteralError::InvalidDigit {
+            digit: '2',
+            radix: 2
+        })
+    );
+    assert_eq!(
+        parse_literal("_1"),
+        Err(LiteralError::InvalidDigit {
+            digit: '_',
+            radix: 10
+        })
+    );
+    assert_eq!(
+        parse_literal("12u7"),
+        Err(LiteralError::UnknownSuffix(String::from("u7")))
+    );
+    assert_eq!(
+        parse_literal("-1u8"),
+        Err(LiteralError::NegativeUnsigned(IntType::U8))
+    );
+    assert_eq!(
+        parse_literal("b'ab'"),
+        Err(LiteralError::InvalidByte(String::from("b'ab'")))
+    );
+}
@@ -0,0 +1,94 @@
+use cpc::numeric::{
+    add_n, checked_add_n, consts, five, plus_one, plus_two, saturating_add_n, Number,
+};
+
+// The boundaries every type shares: the step up to MAX works, one more
+// doesn't fit, and the const versions agree with the generic ones.
+macro_rules! boundaries {
+    ($($t:ident)*) => {$(
+        assert_eq!(plus_one($t::MAX - 1 as $t), $t::MAX, stringify!($t));
+        assert_eq!(plus_two($t::MAX - 2 as $t), $t::MAX, stringify!($t));
+        assert_eq!(five::<$t>(), 5 as $t, stringify!($t));
+
+        assert_eq!(checked_add_n($t::MAX, 1 as $t), None, stringify!($t));
+        assert_eq!(checked_add_n($t::MIN, 0 as $t), Some($t::MIN), stringify!($t));
+        assert_eq!(saturating_add_n($t::MAX, 1 as $t), $t::MAX, stringify!($t));
+        assert_eq!(saturating_add_n($t::MAX, $t::MAX), $t::MAX, stringify!($t));
+        assert_eq!(saturating_add_n($t::MIN, 0 as $t), $t::MIN, stringify!($t));
+
+        assert_eq!(consts::$t::plus_one($t::MAX - 1 as $t), $t::MAX, stringify!($t));
+        assert_eq!(consts::$t::checked_add_n($t::MAX, 1 as $t), None, stringify!($t));
+        assert_eq!(consts::$t::saturating_add_n($t::MAX, 1 as $t), $t::MAX, stringify!($t));
+        assert_eq!(consts::$t::five(), five::<$t>(), stringify!($t));
+    )*};
+}
+
+#[test]
+fn integers_stop_at_their_boundaries() {
+    boundaries!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+}
+
+#[test]
+fn signed_integers_saturate_at_min() {
+    macro_rules! signed {
+        ($($t:ident)*) => {$(
+            assert_eq!(checked_add_n($t::MIN, -1), None, stringify!($t));
+            assert_eq!(saturating_add_n($t::MIN, -1), $t::MIN, stringify!($t));
+            assert_eq!(plus_one($t::MIN), $t::MIN + 1, stringify!($t));
+            assert_eq!(consts::$t::saturating_add_n($t::MIN, $t::MIN), $t::MIN);
+        )*};
+    }
+    signed!(i8 i16 i32 i64 i128 isize);
+}
+
+#[test]
+fn floats_overflow_to_infinity_not_past_max() {
+    // A single 1.0 is lost to rounding at MAX, it takes MAX to overflow.
+    macro_rules! floats {
+        ($($t:ident)*) => {$(
+            assert_eq!(add_n($t::MAX, 1.0), $t::MAX);
+            assert_eq!(add_n($t::MAX, $t::MAX), $t::INFINITY);
+            assert_eq!(checked_add_n($t::MAX, $t::MAX), None);
+            assert_eq!(checked_add_n($t::MIN, $t::MIN), None);
+            assert_eq!(checked_add_n($t::MAX, $t::MIN), Some(0.0));
+            assert_eq!(saturating_add_n($t::MAX, $t::MAX), $t::MAX);
+            assert_eq!(saturating_add_n($t::MIN, $t::MIN), $t::MIN);
+            assert!(saturating_add_n($t::NAN, 1.0).is_nan());
+            assert_eq!(plus_one($t::MIN_POSITIVE), 1.0);
+
+            assert_eq!(consts::$t::checked_add_n($t::MAX, $t::MAX), None);
+            assert_eq!(consts::$t::saturating_add_n($t::MIN, $t::MIN), $t::MIN);
+            assert_eq!(consts::$t::plus_two(0.5), 2.5);
+        )*};
+    }
+    floats!(f32 f64);
+}
+
+#[test]
+fn helpers_work_in_consts() {
+    const LAST: u8 = consts::u8::plus_one(254);
+    const NEXT: Option<i32> = consts::i32::checked_add_n(i32::MAX, 1);
+    const CLAMPED: i64 = consts::i64::saturating_add_n(i64::MIN, -5);
+    const HALF: f32 = consts::f32::add_n(0.25, 0.25);
+
+    assert_eq!(LAST, u8::MAX);
+    assert_eq!(NEXT, None);
+    assert_eq!(CLAMPED, i64::MIN);
+    assert_eq!(HALF, 0.5);
+}
+
+#[test]
+fn small_numbers_fit_where_they_can() {
+    assert_eq!(i8::from_u8(127), Some(127));
+    assert_eq!(i8::from_u8(128), None);
+    assert_eq!(u8::from_u8(255), Some(255));
+    assert_eq!(f32::from_u8(255), Some(255.0));
+}
+
+// Release builds wrap instead of panicking, like + does.
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "overflow")]
+fn add_n_overflows_like_plus() {
+    add_n(i32::MAX, std::hint::black_box(1));
+}
@@ -0,0 +1,130 @@
+use cpc::ints::IntValue;
+use cpc::overflow::{evaluate, explain, Mode, Op, Outcome, Results};
+
+fn all_four(wrapping: IntValue, checked: Option<IntValue>, saturating: Outcome) -> Results {
+    Results {
+        wrapping: Outcome::Value(wrapping),
+        checked: Outcome::Checked(checked),
+        saturating,
+        overflowing: Outcome::Overflowing(wrapping, checked.is_none()),
+    }
+}
+
+#[test]
+fn every_mode_handles_a_u8_sum() {
+    let results = evaluate(IntValue::U8(200), Op::Add, IntValue::U8(100));
+    assert_eq!(
+        results,
+        all_four(IntValue::U8(44), None, Outcome::Value(IntValue::U8(255)))
+    );
+    assert!(results.overflowed());
+
+    let fits = evaluate(IntValue::U8(200), Op::Sub, IntValue::U8(100));
+    assert!(!fits.overflowed());
+    for mode in Mode::ALL {
+        assert_ne!(fits.get(mode), Outcome::Missing, "{}", mode.name());
+    }
+}
+
+#[test]
+fn min_divided_by_minus_one_overflows() {
+    assert_eq!(
+        evaluate(IntValue::I8(i8::MIN), Op::Div, IntValue::I8(-1)),
+        all_four(
+            IntValue::I8(i8::MIN),
+            None,
+            Outcome::Value(IntValue::I8(i8::MAX))
+        )
+    );
+    assert_eq!(
+        evaluate(IntValue::I128(i128::MIN), Op::Div, IntValue::I128(-1)).checked,
+        Outcome::Checked(None)
+    );
+    assert_eq!(
+        evaluate(IntValue::Isize(i64::MIN), Op::Div, IntValue::Isize(-1)).wrapping,
+        Outcome::Value(IntValue::Isize(i64::MIN))
+    );
+
+    // The remainder is 0, but computing it still overflows.
+    let remainder = evaluate(IntValue::I32(i32::MIN), Op::Rem, IntValue::I32(-1));
+    assert_eq!(
+        remainder,
+        all_four(IntValue::I32(0), None, Outcome::Missing)
+    );
+
+    // Unlike +, a plain / or % panics on overflow even in release builds.
+    let quotient = evaluate(IntValue::I8(i8::MIN), Op::Div, IntValue::I8(-1));
+    assert_eq!(
+        explain("-128", Op::Div, "-1", &quotient),
+        "-128 / -1 overflows i8, whose range is -128..=127: a plain / panics with\n\
+         \"attempt to divide with overflow\" in every build, division overflow is always checked."
+    );
+    let explained = explain("-2147483648", Op::Rem, "-1", &remainder);
+    assert!(
+        explained.contains("\"attempt to calculate the remainder with overflow\" in every build")
+    );
+    assert!(!explained.contains("release"));
+}
+
+#[test]
+fn explanations_match_what_the_plain_operator_does() {
+    let sum = evaluate(IntValue::U8(200), Op::Add, IntValue::U8(100));
+    assert_eq!(
+        explain("200", Op::Add, "100", &sum),
+        "200 + 100 overflows u8, whose range is 0..=255: a plain + panics with\n\
+         \"attempt to add with overflow\" in debug builds and gives 44 in release builds."
+    );
+
+    let fits = evaluate(IntValue::U8(3), Op::Add, IntValue::U8(4));
+    assert_eq!(
+        explain("3", Op::Add, "4", &fits),
+        "3 + 4 = 7 fits in u8, so every mode agrees."
+    );
+
+    let by_zero = evaluate(IntValue::U8(7), Op::Div, IntValue::U8(0));
+    assert_eq!(
+        explain("7", Op::Div, "0", &by_zero),
+        "7 / 0 panics with \"attempt to divide by zero\" in every build."
+    );
+}
+
+#[test]
+fn shift_amounts_wrap_at_the_bit_width() {
+    assert_eq!(
+        evaluate(IntValue::U8(1), Op::Shl, IntValue::U32(7)),
+        all_four(IntValue::U8(128), Some(IntValue::U8(128)), Outcome::Missing)
+    );
+    // Only the amount is masked, 1 << 8 becomes 1 << 0.
+    assert_eq!(
+        evaluate(IntValue::U8(1), Op::Shl, IntValue::U32(8)),
+        all_four(IntValue::U8(1), None, Outcome::Missing)
+    );
+    assert_eq!(
+        evaluate(IntValue::I32(-8), Op::Shr, IntValue::U32(33)),
+        all_four(IntValue::I32(-4), None, Outcome::Missing)
+    );
+    assert_eq!(
+        evaluate(IntValue::U64(3), Op::Pow, IntValue::U32(41)).checked,
+        Outcome::Checked(None)
+    );
+}
+
+#[test]
+fn dividing_by_zero_panics_unless_checked() {
+    let message = "attempt to divide by zero";
+    assert_eq!(
+        evaluate(IntValue::U16(7), Op::Div, IntValue::U16(0)),
+        Results {
+            wrapping: Outcome::Panic(message),
+            checked: Outcome::Checked(None),
+            saturating: Outcome::Panic(message),
+            overflowing: Outcome::Panic(message),
+        }
+    );
+
+    let remainder = evaluate(IntValue::I64(7), Op::Rem, IntValue::I64(0));
+    assert_eq!(remainder.checked, Outcome::Checked(None));
+    assert_eq!(remainder.saturating, Outcome::Missing);
+    assert!(matches!(remainder.wrapping, Outcome::Panic(_)));
+    assert!(!remainder.overflowed());
+}
@@ -0,0 +1,80 @@
+use cpc::recurrence::{Evaluation, LinearRecurrence, RecurrenceError, NAMES};
+
+fn first(recurrence: &LinearRecurrence, count: usize) -> Vec<String> {
+    recurrence
+        .terms()
+        .take(count)
+        .map(|term| term.to_string())
+        .collect()
+}
+
+#[test]
+fn iterative_and_matrix_agree() {
+    let custom = LinearRecurrence::new(vec![3, 0, 2], vec![1, 4, 9]).unwrap();
+    let recurrences = NAMES
+        .iter()
+        .map(|name| LinearRecurrence::named(name).unwrap())
+        .chain([custom]);
+
+    for recurrence in recurrences {
+        for n in (0..60).chain([100, 257, 1_000]) {
+            assert_eq!(
+                recurrence.nth_iterative(n),
+                recurrence.nth_matrix(n),
+                "{:?} n = {n}",
+                recurrence.coefficients()
+            );
+        }
+    }
+}
+
+#[test]
+fn named_sequences_match_their_known_values() {
+    assert_eq!(
+        first(&LinearRecurrence::lucas(), 11),
+        ["2", "1", "3", "4", "7", "11", "18", "29", "47", "76", "123"]
+    );
+    assert_eq!(
+        first(&LinearRecurrence::pell(), 11),
+        ["0", "1", "2", "5", "12", "29", "70", "169", "408", "985", "2378"]
+    );
+    assert_eq!(
+        first(&LinearRecurrence::padovan(), 11),
+        ["1", "1", "1", "2", "2", "3", "4", "5", "7", "9", "12"]
+    );
+    assert_eq!(
+        first(&LinearRecurrence::tribonacci(), 11),
+        ["0", "0", "1", "1", "2", "4", "7", "13", "24", "44", "81"]
+    );
+
+    let nth = |recurrence: LinearRecurrence, n| recurrence.nth(n, Evaluation::Matrix).to_string();
+    assert_eq!(nth(LinearRecurrence::lucas(), 100), "792070839848372253127");
+    assert_eq!(
+        nth(LinearRecurrence::pell(), 100),
+        "66992092050551637663438906713182313772"
+    );
+    assert_eq!(nth(LinearRecurrence::padovan(), 100), "1177482265857");
+    assert_eq!(
+        nth(LinearRecurrence::fibonacci(), 100),
+        "354224848179261915075"
+    );
+}
+
+#[test]
+fn malformed_recurrences_are_rejected() {
+    assert_eq!(
+        LinearRecurrence::new(vec![], vec![]),
+        Err(RecurrenceError::Empty)
+    );
+    assert_eq!(
+        LinearRecurrence::new(vec![1, 1], vec![0, 1, 1]),
+        Err(RecurrenceError::LengthMismatch {
+            coefficients: 2,
+            seeds: 3
+        })
+    );
+    assert_eq!(
+        LinearRecurrence::named("catalan"),
+        Err(RecurrenceError::UnknownSequence(String::from("catalan")))
+    );
+}
@@ -0,0 +1,161 @@
+use cpc::rational::Rational;
+use cpc::temperature::{table, Scale, Temperature, TemperatureError};
+
+fn temperature(value: &str, scale: Scale) -> Temperature {
+    Temperature::new(value.parse().unwrap(), scale).unwrap()
+}
+
+#[test]
+fn conversions_round_trip_exactly() {
+    // Whole degrees and awkward decimals, from absolute zero up.
+    let values = (0..2_000).map(|i| Rational::integer(i - 459)).chain(
+        ["0.01", "98.6", "37.777", "-273.15", "1e-9", "1234.5678"].map(|v| v.parse().unwrap()),
+    );
+
+    for value in values {
+        for from in Scale::ALL {
+            let Ok(start) = Temperature::new(value, from) else {
+                continue;
+            };
+            for to in Scale::ALL {
+                let there = start.to(to);
+                assert_eq!(there.to(from), start, "{start} -> {to} -> {from}");
+                assert_eq!(there.kelvin(), start.kelvin());
+            }
+        }
+    }
+}
+
+#[test]
+fn well_known_temperatures_match() {
+    let same = [
+        (
+            temperature("-40", Scale::Celsius),
+            temperature("-40", Scale::Fahrenheit),
+        ),
+        (
+            temperature("100", Scale::Celsius),
+            temperature("212", Scale::Fahrenheit),
+        ),
+        (
+            temperature("0", Scale::Celsius),
+            temperature("273.15", Scale::Kelvin),
+        ),
+        (
+            temperature("0", Scale::Celsius),
+            temperature("491.67", Scale::Rankine),
+        ),
+        (
+            temperature("0", Scale::Kelvin),
+            temperature("-459.67", Scale::Fahrenheit),
+        ),
+        (
+            temperature("37", Scale::Celsius),
+            temperature("98.6", Scale::Fahrenheit),
+        ),
+    ];
+
+    for (a, b) in same {
+        assert_eq!(a.to(b.scale()), b, "{a} should be {b}");
+        assert_eq!(b.to(a.scale()), a, "{b} should be {a}");
+    }
+
+    // 100 °F isn't a terminating decimal in Celsius, but it is exact.
+    let celsius = temperature("100", Scale::Fahrenheit).to(Scale::Celsius);
+    assert_eq!(celsius.value(), Rational::new(340, 9));
+    assert_eq!(celsius.to_string(), "37.78 °C");
+}
+
+#[test]
+fn tables_step_from_start_to_end() {
+    let rows = table(
+        Rational::integer(-40),
+        Rational::integer(100),
+        Rational::integer(10),
+        Scale::Celsius,
+    )
+    .unwrap();
+
+    assert_eq!(rows.len(), 15);
+    assert_eq!(
+        rows[0].to(Scale::Fahrenheit),
+        temperature("-40", Scale::Fahrenheit)
+    );
+    assert_eq!(
+        rows[14].to(Scale::Fahrenheit),
+        temperature("212", Scale::Fahrenheit)
+    );
+
+    // The end is left out when no step lands on it.
+    let rows = table(
+        Rational::ZERO,
+        Rational::ONE,
+        "0.3".parse().unwrap(),
+        Scale::Fahrenheit,
+    )
+    .unwrap();
+    let values: Vec<String> = rows.iter().map(|row| row.to_string()).collect();
+    assert_eq!(values, ["0 °F", "0.3 °F", "0.6 °F", "0.9 °F"]);
+}
+
+#[test]
+fn impossible_temperatures_are_rejected() {
+    let below = Temperature::new(Rational::integer(-274), Scale::Celsius);
+    assert!(matches!(
+        below,
+        Err(TemperatureError::BelowAbsoluteZero { .. })
+    ));
+    assert!(Temperature::new(Rational::new(-1, 1_000_000), Scale::Kelvin).is_err());
+
+    let ten = Rational::integer(10);
+    assert!(matches!(
+        table(ten, Rational::ZERO, ten, Scale::Celsius),
+        Err(TemperatureError::Reversed { .. })
+    ));
+    assert!(matches!(
+        table(Rational::ZERO, ten, Rational::ZERO, Scale::Celsius),
+        Err(TemperatureError::InvalidStep(_))
+    ));
+    assert!(matches!(
+        table(Rational::integer(-300), ten, ten, Scale::Celsius),
+        Err(TemperatureError::BelowAbsoluteZero { .. })
+    ));
+    assert_eq!(
+        "X".parse::<Scale>(),
+        Err(TemperatureError::UnknownScale("X".into()))
+    );
+
+    // Far too large, or too finely divided, to convert without overflowing.
+    for value in [
+        "99999999999999999999999999999999999",
+        "1/170141183460469231731687303715884105727",
+    ] {
+        assert!(matches!(
+            Temperature::new(value.parse().unwrap(), Scale::Fahrenheit),
+            Err(TemperatureError::OutOfRange { .. })
+        ));
+    }
+    assert!(matches!(
+        table(
+            Rational::ZERO,
+            "99999999999999999999999999999999999".parse().unwrap(),
+            "999999999999999999999999999999999".parse().unwrap(),
+            Scale::Fahrenheit
+        ),
+        Err(TemperatureError::OutOfRange { .. })
+    ));
+}
+
+#[test]
+fn decimals_round_without_overflowing() {
+    assert_eq!(Rational::new(5, 9).to_decimal(2), "0.56");
+    assert_eq!(Rational::new(-1, 2).to_decimal(0), "-1");
+    assert_eq!(Rational::new(-1, 1000).to_decimal(2), "0");
+    assert_eq!(Rational::new(1999, 1000).to_decimal(2), "2");
+    assert_eq!(
+        Rational::integer(i128::MAX).to_decimal(6),
+        i128::MAX.to_string()
+    );
+    assert_eq!(Rational::new(1, i128::MAX).to_decimal(6), "0");
+    assert_eq!(Rational::new(i128::MAX, i128::MAX - 1).to_decimal(3), "1");
+}
@@ -0,0 +1,94 @@
+use cpc::units::{Dimension, PrefixedUnit, Quantity, UnitError};
+
+fn convert(value: f64, from: &str, to: &str) -> f64 {
+    Quantity::new(value, from)
+        .unwrap()
+        .convert_to(to.parse().unwrap())
+        .unwrap()
+        .value
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() <= expected.abs() * 1e-12,
+        "{actual} != {expected}"
+    );
+}
+
+#[test]
+fn compatible_units_convert() {
+    assert_close(convert(5.0, "m", "ft"), 16.404_199_475_065_617);
+    assert_close(convert(1.0, "mi", "km"), 1.609_344);
+    assert_close(convert(1.0, "lb", "oz"), 16.0);
+    assert_close(convert(90.0, "min", "h"), 1.5);
+    assert_close(convert(1.0, "yr", "d"), 365.25);
+    assert_close(convert(1.0, "nibble", "bit"), 4.0);
+}
+
+#[test]
+fn temperatures_convert_with_their_offsets() {
+    assert_close(convert(-40.0, "°C", "°F"), -40.0);
+    assert_close(convert(100.0, "celsius", "K"), 373.15);
+    assert_close(convert(0.0, "°F", "°R"), 459.67);
+
+    // The offset is in kelvin, the prefix only applies afterwards.
+    assert_close(convert(0.0, "°C", "mK"), 273_150.0);
+    assert_close(convert(1.0, "kK", "°C"), 726.85);
+    assert_close(convert(5.0, "mK", "K"), 0.005);
+
+    assert_eq!(
+        Quantity::new(-300.0, "°C"),
+        Err(UnitError::BelowAbsoluteZero(String::from("-300 °C")))
+    );
+    assert!(Quantity::new(-1.0, "mK").is_err());
+}
+
+#[test]
+fn prefixes_scale_units() {
+    assert_close(convert(2.5, "km", "m"), 2_500.0);
+    assert_close(convert(1.0, "µs", "ns"), 1_000.0);
+    assert_close(convert(1.0, "us", "ms"), 0.001);
+    assert_close(convert(1.0, "dam", "m"), 10.0);
+    assert_close(convert(1.0, "KiB", "B"), 1_024.0);
+    assert_close(convert(1.0, "GiB", "MB"), 1_073.741_824);
+    assert_close(convert(1.0, "kbit", "B"), 125.0);
+
+    // A whole unit name wins over a prefix, min is a minute.
+    let minute: PrefixedUnit = "min".parse().unwrap();
+    assert_eq!((minute.prefix, minute.unit.name), (None, "minute"));
+    assert_eq!("km".parse::<PrefixedUnit>().unwrap().to_string(), "km");
+}
+
+#[test]
+fn bad_units_are_rejected() {
+    assert_eq!(
+        "kft".parse::<PrefixedUnit>(),
+        Err(UnitError::NoPrefixes {
+            prefix: "k",
+            unit: "ft"
+        })
+    );
+    assert_eq!(
+        "Kim".parse::<PrefixedUnit>(),
+        Err(UnitError::BinaryPrefix {
+            prefix: "Ki",
+            unit: "m"
+        })
+    );
+    assert_eq!(
+        "parsec".parse::<PrefixedUnit>(),
+        Err(UnitError::UnknownUnit(String::from("parsec")))
+    );
+
+    let mass = Quantity::new(3.0, "kg").unwrap();
+    let error = mass.convert_to("s".parse().unwrap()).unwrap_err();
+    assert!(matches!(
+        error,
+        UnitError::Incompatible { from, to }
+            if from.dimension() == Dimension::Mass && to.dimension() == Dimension::Time
+    ));
+    assert_eq!(
+        error.to_string(),
+        "cannot convert `kg` to `s`: kg measures mass but s measures time"
+    );
+}
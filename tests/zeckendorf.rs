@@ -0,0 +1,70 @@
+use cpc::fibonnacci::is_fibonacci;
+use cpc::zeckendorf::{codeword, decode, decode_bits, encode, zeckendorf, FibCodeError};
+
+fn bits(text: &str) -> Vec<bool> {
+    text.chars().map(|c| c == '1').collect()
+}
+
+#[test]
+fn zeckendorf_sums_are_unique_non_consecutive_fibonacci_numbers() {
+    for n in (1..5_000).chain([u64::MAX - 1, u64::MAX]) {
+        let terms = zeckendorf(n);
+
+        assert_eq!(terms.iter().map(|(_, value)| value).sum::<u64>(), n);
+        assert!(terms.iter().all(|&(_, value)| is_fibonacci(value)));
+        assert!(
+            terms.windows(2).all(|pair| pair[0].0 >= pair[1].0 + 2),
+            "{n}: {terms:?}"
+        );
+    }
+
+    assert_eq!(zeckendorf(100), [(11, 89), (6, 8), (4, 3)]);
+    assert!(zeckendorf(0).is_empty());
+}
+
+#[test]
+fn codewords_match_the_textbook_table() {
+    let table = [
+        (1, "11"),
+        (2, "011"),
+        (3, "0011"),
+        (4, "1011"),
+        (5, "00011"),
+        (6, "10011"),
+        (7, "01011"),
+        (8, "000011"),
+        (11, "001011"),
+        (12, "101011"),
+    ];
+
+    for (value, code) in table {
+        assert_eq!(codeword(value), Ok(bits(code)), "{value}");
+        assert_eq!(decode_bits(bits(code)), Ok(vec![value]));
+    }
+}
+
+#[test]
+fn byte_streams_round_trip() {
+    let values: Vec<u64> = (1..2_000)
+        .chain([u32::MAX.into(), u64::MAX / 3, u64::MAX - 1, u64::MAX])
+        .collect();
+
+    let bytes = encode(&values).unwrap();
+    assert_eq!(decode(&bytes), Ok(values));
+
+    for value in [1, 2, 3, 4, 100, u64::MAX] {
+        assert_eq!(decode(&encode(&[value]).unwrap()), Ok(vec![value]));
+    }
+
+    assert_eq!(decode(&encode(&[]).unwrap()), Ok(vec![]));
+}
+
+#[test]
+fn invalid_input_is_rejected() {
+    assert_eq!(encode(&[3, 0, 5]), Err(FibCodeError::Zero));
+    assert_eq!(decode_bits(bits("1011101")), Err(FibCodeError::Truncated));
+
+    // 93 zeros followed by "11" would need F(95), which doesn't fit in a u64.
+    let too_large = bits(&format!("{}11", "0".repeat(93)));
+    assert_eq!(decode_bits(too_large), Err(FibCodeError::Overflow));
+}